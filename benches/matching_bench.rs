@@ -125,7 +125,7 @@ fn bench_cancel_from_deep_book(c: &mut Criterion) {
             || {
                 let mut engine = MatchingEngine::new();
                 // Add 100 orders
-                for i in 1..=100 {
+                for _ in 1..=100 {
                     let order = Order::limit("AAPL".to_string(), Side::Buy, 15000, 100);
                     engine.submit_order(order).unwrap();
                 }
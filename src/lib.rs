@@ -3,5 +3,5 @@ pub mod orderbook;
 pub mod matching;
 
 // Re-export important types for clean external use
-pub use matching::MatchingEngine;
-pub use types::{Order, Side};
+pub use matching::{EngineListener, MarketOrderPolicy, MassCancelFilter, MatchingEngine};
+pub use types::{ExecutionReport, Order, OrderError, OrderState, OrderStatus, PegType, PostOnly, SelfTradePrevention, Side, TimeInForce, Trade, TrailingOffset};
@@ -26,10 +26,10 @@ fn main() {
 
     for order in orders {
         match engine.submit_order(order) {
-            Ok(trades) => {
-                if !trades.is_empty() {
-                    println!("Trades executed:");
-                    for trade in trades {
+            Ok(report) => {
+                if !report.trades.is_empty() {
+                    println!("Trades executed for order #{}:", report.order_id);
+                    for trade in report.trades {
                         println!("{:?}", trade);
                     }
                 }
@@ -1,24 +1,142 @@
-use crate::types::{Order, OrderType, OrderId, Symbol, Trade, Side, Price};
-use crate::orderbook::OrderBook;
-use std::collections::HashMap;
+use crate::types::{AccountId, ClientOrderId, ExecutionReport, Order, OrderError, OrderState, OrderStatus, OrderType, OrderId, PostOnly, SelfTradePrevention, Symbol, Trade, Side, Price, Quantity, TimeInForce};
+use crate::orderbook::{OrderBook, SequencedEvent, TriggerBook};
+use super::groups::OrderGroups;
+use super::listener::EngineListener;
+use std::collections::{HashMap, VecDeque};
 use std::time::{SystemTime, UNIX_EPOCH};
 
+/// What happens to the part of a market order that can't be filled immediately
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MarketOrderPolicy {
+    /// Cancel the unfilled remainder and report it in `cancelled_quantity`
+    #[default]
+    CancelRemainder,
+    /// Reject market orders outright when the opposite side of the book is empty;
+    /// any remainder after a partial fill is cancelled
+    RejectIfNoLiquidity,
+    /// Convert the remainder to a limit order at the last fill price (market-to-limit);
+    /// cancelled if nothing filled
+    ConvertToLimit,
+}
+
+/// Which resting orders a mass cancel removes; unset fields match everything
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MassCancelFilter {
+    pub symbol: Option<Symbol>,
+    pub side: Option<Side>,
+    pub owner: Option<AccountId>,
+    /// Lowest price to cancel (inclusive)
+    pub min_price: Option<Price>,
+    /// Highest price to cancel (inclusive)
+    pub max_price: Option<Price>,
+}
+
+impl MassCancelFilter {
+    /// Check if a resting order matches every set field
+    pub fn matches(&self, order: &Order) -> bool {
+        let price = order.price.unwrap_or(0);
+        self.symbol.as_ref().is_none_or(|symbol| *symbol == order.symbol)
+            && self.side.is_none_or(|side| side == order.side)
+            && self.owner.as_ref().is_none_or(|owner| order.owner.as_ref() == Some(owner))
+            && self.min_price.is_none_or(|min| price >= min)
+            && self.max_price.is_none_or(|max| price <= max)
+    }
+}
+
+/// Finished orders whose status is kept by default (see `with_status_retention`)
+const DEFAULT_STATUS_RETENTION: usize = 100_000;
+
+/// Owning account and client order ID; client IDs are unique per account
+type ClientOrderKey = (Option<AccountId>, ClientOrderId);
+
+/// What the engine remembers about every order it has assigned an ID to
+struct OrderRecord {
+    symbol: Symbol,
+    side: Side,
+    original_quantity: Quantity,
+    quantity: Quantity,         // Total quantity after amendments (filled + open)
+    filled_quantity: Quantity,
+    notional: u128,             // Sum of fill price * quantity, for the average price
+    state: OrderState,          // Only meaningful once terminal; live states are derived
+}
+
 pub struct MatchingEngine {
     order_books: HashMap<Symbol, OrderBook>,  // One book per symbol
+    trigger_books: HashMap<Symbol, TriggerBook>,  // Pending stop orders per symbol
+    last_trade_prices: HashMap<Symbol, Price>,
+    market_order_policy: MarketOrderPolicy,
+    market_data: bool,  // Record book events in every order book
+    order_groups: OrderGroups,  // OCO links and pending bracket exits
+    order_index: HashMap<OrderId, (Symbol, Option<ClientOrderKey>)>,  // Every live order, for lookups by ID alone
+    client_order_ids: HashMap<ClientOrderKey, OrderId>,  // Live orders by owner and client ID
+    order_records: HashMap<OrderId, OrderRecord>,  // Fill history and state of live and recently finished orders
+    finished_orders: VecDeque<OrderId>,  // Finished orders with a record, oldest first
+    status_retention: usize,  // Most finished orders to keep records for
+    listeners: Vec<Box<dyn EngineListener>>,  // Notified of order, trade and BBO events
+    last_bbo: HashMap<Symbol, (Option<Price>, Option<Price>)>,  // Best bid/ask listeners last saw
     next_trade_id: u64,
     next_order_id: u64,
 }
 
+impl Default for MatchingEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl MatchingEngine {
     /// Create a new matching engine
     pub fn new() -> Self {
         MatchingEngine {
             order_books: HashMap::new(),
+            trigger_books: HashMap::new(),
+            last_trade_prices: HashMap::new(),
+            market_order_policy: MarketOrderPolicy::default(),
+            market_data: false,
+            order_groups: OrderGroups::default(),
+            order_index: HashMap::new(),
+            client_order_ids: HashMap::new(),
+            order_records: HashMap::new(),
+            finished_orders: VecDeque::new(),
+            status_retention: DEFAULT_STATUS_RETENTION,
+            listeners: Vec::new(),
+            last_bbo: HashMap::new(),
             next_trade_id: 1,
             next_order_id: 1,
         }
     }
 
+    /// Set how unfilled market order quantity is handled
+    pub fn with_market_order_policy(mut self, policy: MarketOrderPolicy) -> Self {
+        self.market_order_policy = policy;
+        self
+    }
+
+    /// Set how many finished orders keep their status for `order_status`
+    /// (100,000 by default)
+    ///
+    /// Records of live orders are always kept. Once more than `retention`
+    /// orders have finished, the oldest finished records are dropped, so
+    /// memory stays bounded under continuous trading.
+    pub fn with_status_retention(mut self, retention: usize) -> Self {
+        self.status_retention = retention;
+        self
+    }
+
+    /// Record a sequenced market data event stream per symbol (see `take_market_data`)
+    pub fn with_market_data(mut self) -> Self {
+        self.market_data = true;
+        for book in self.order_books.values_mut() {
+            book.enable_events();
+        }
+        self
+    }
+
+    /// Register a listener for order, trade and BBO events (see `EngineListener`)
+    pub fn add_listener(&mut self, listener: Box<dyn EngineListener>) {
+        self.listeners.push(listener);
+    }
+
     fn get_timestamp() -> u64 {
         SystemTime::now()
             .duration_since(UNIX_EPOCH)
@@ -26,72 +144,393 @@ impl MatchingEngine {
             .as_nanos() as u64
     }
 
-    pub fn submit_order(&mut self, mut order: Order) -> Result<Vec<Trade>, String> {
+    pub fn submit_order(&mut self, order: Order) -> Result<ExecutionReport, OrderError> {
+        let order = self.prepare_order(order)?;
+
+        // Process
+        Ok(self.process_order(order))
+    }
+
+    /// Submit two orders as a one-cancels-other pair
+    ///
+    /// A fill on either leg cancels the other, as does either leg being
+    /// cancelled or expiring. Both legs must be able to rest (no market,
+    /// IOC or FOK orders), trade the same symbol and, if on opposite sides,
+    /// not cross each other; the legs never trade with each other. If the
+    /// first leg fills on arrival the second is cancelled without being submitted.
+    pub fn submit_oco(
+        &mut self,
+        first: Order,
+        second: Order,
+    ) -> Result<(ExecutionReport, ExecutionReport), OrderError> {
+        if first.client_order_id.is_some() && self.client_order_key(&first) == self.client_order_key(&second) {
+            let error = OrderError::DuplicateClientOrderId;
+            self.notify_reject(&second, &error);
+            return Err(error);
+        }
+
+        for leg in [&first, &second] {
+            if leg.symbol != first.symbol
+                || leg.order_type == OrderType::Market
+                || leg.time_in_force.is_immediate()
+            {
+                let error = OrderError::InvalidOrderGroup;
+                self.notify_reject(leg, &error);
+                return Err(error);
+            }
+        }
+
+        // Opposite-side legs that cross would both fill from one trade
+        let (buy, sell) = match first.side {
+            Side::Buy => (&first, &second),
+            Side::Sell => (&second, &first),
+        };
+        if buy.side != sell.side && buy.price.zip(sell.price).is_some_and(|(bid, ask)| bid >= ask) {
+            let error = OrderError::InvalidOrderGroup;
+            self.notify_reject(&second, &error);
+            return Err(error);
+        }
+
+        let first = self.prepare_order(first)?;
+        let second = match self.prepare_order(second) {
+            Ok(second) => second,
+            Err(e) => {
+                self.finish_record(first.id, OrderState::Rejected);
+                self.notify_reject(&first, &e);
+                return Err(e);
+            }
+        };
+
+        Ok(self.process_linked(first, second))
+    }
+
+    /// Submit an entry order with a take-profit limit and stop-loss stop exit
+    ///
+    /// Every request that fills the entry (in whole or in part) submits an
+    /// exit pair on the opposite side for the quantity it filled, as a
+    /// one-cancels-other pair, so filled quantity is never left unprotected.
+    /// Exits are reported in the `triggered` list of that request. An entry
+    /// that never fills discards its exits. Exits never trade with their own
+    /// entry, and for an entry with a limit price the take-profit must be above
+    /// it (below for a sell entry) and the stop-loss below it (above).
+    pub fn submit_bracket(
+        &mut self,
+        entry: Order,
+        take_profit: Price,
+        stop_loss: Price,
+    ) -> Result<ExecutionReport, OrderError> {
+        // Exits priced through the entry's limit would cross its resting remainder
+        let exits_straddle_entry = match (entry.side, entry.price) {
+            (Side::Buy, Some(limit)) => take_profit > limit && stop_loss < limit,
+            (Side::Sell, Some(limit)) => take_profit < limit && stop_loss > limit,
+            (_, None) => true,
+        };
+        if !exits_straddle_entry {
+            let error = OrderError::InvalidOrderGroup;
+            self.notify_reject(&entry, &error);
+            return Err(error);
+        }
+
+        let exit_side = entry.side.opposite();
+        let mut take_profit = Order::limit(entry.symbol.clone(), exit_side, take_profit, entry.quantity);
+        let mut stop_loss = Order::stop(entry.symbol.clone(), exit_side, stop_loss, entry.quantity);
+        for exit in [&mut take_profit, &mut stop_loss] {
+            exit.owner = entry.owner.clone();
+            exit.self_trade_prevention = entry.self_trade_prevention;
+            if let Err(e) = Self::validate_order(exit) {
+                self.notify_reject(&entry, &e);
+                return Err(e);
+            }
+        }
+
+        let entry = self.prepare_order(entry)?;
+        self.order_groups.add_bracket(entry.id, take_profit, stop_loss);
+
+        Ok(self.process_order(entry))
+    }
+
+    /// Validate an incoming order, resolve its starting price and assign its ID and timestamp
+    fn prepare_order(&mut self, mut order: Order) -> Result<Order, OrderError> {
+        if let Err(e) = self.check_order(&mut order) {
+            self.notify_reject(&order, &e);
+            return Err(e);
+        }
 
-        // Validate order
-        Self::validate_order(&order)?;   // if Err, returns early
-    
         // Assign ID and timestamp
         order.id = self.next_order_id;
         self.next_order_id += 1;
         order.timestamp = Self::get_timestamp();
-    
-        // Process
-        Ok(self.process_order(order))
+
+        self.order_records.insert(order.id, OrderRecord {
+            symbol: order.symbol.clone(),
+            side: order.side,
+            original_quantity: order.quantity,
+            quantity: order.quantity,
+            filled_quantity: 0,
+            notional: 0,
+            state: OrderState::New,
+        });
+
+        for listener in &mut self.listeners {
+            listener.on_accept(&order);
+        }
+
+        Ok(order)
+    }
+
+    /// Run every acceptance check on an incoming order, resolving trailing
+    /// stop triggers and pegged prices
+    fn check_order(&self, order: &mut Order) -> Result<(), OrderError> {
+        // Validate order
+        Self::validate_order(order)?;   // if Err, returns early
+
+        if self.client_order_key(order).is_some_and(|key| self.client_order_ids.contains_key(&key)) {
+            return Err(OrderError::DuplicateClientOrderId);
+        }
+
+        if order.order_type == OrderType::Market
+            && self.market_order_policy == MarketOrderPolicy::RejectIfNoLiquidity
+        {
+            let opposite_best = self.order_books
+                .get(&order.symbol)
+                .and_then(|book| book.best_executable_price(order.side.opposite()));
+            if opposite_best.is_none() {
+                return Err(OrderError::NoLiquidity);
+            }
+        }
+
+        self.apply_post_only(order)?;
+
+        // Trailing stops start their trail from the last trade price
+        if order.order_type == OrderType::TrailingStop {
+            let last_price = self.last_trade_price(&order.symbol)
+                .ok_or(OrderError::NoReferencePrice)?;
+            order.trigger_price = order.trailing_trigger(last_price);
+        }
+
+        // Pegged orders start at their current pegged price
+        if order.order_type == OrderType::Pegged {
+            order.price = Some(self.peg_price(order).ok_or(OrderError::NoReferencePrice)?);
+        }
+
+        Ok(())
+    }
+
+    fn notify_reject(&mut self, order: &Order, reason: &OrderError) {
+        for listener in &mut self.listeners {
+            listener.on_reject(order, reason);
+        }
+    }
+
+    fn notify_amend_reject(&mut self, order_id: OrderId, reason: &OrderError) {
+        for listener in &mut self.listeners {
+            listener.on_amend_reject(order_id, reason);
+        }
+    }
+
+    /// Tell listeners about a symbol's best bid/ask if it changed since they last heard
+    fn notify_bbo(&mut self, symbol: &str) {
+        if self.listeners.is_empty() {
+            return;
+        }
+        let bbo = self.order_books
+            .get(symbol)
+            .map_or((None, None), |book| (book.best_bid(), book.best_ask()));
+        if self.last_bbo.get(symbol).copied().unwrap_or_default() == bbo {
+            return;
+        }
+
+        self.last_bbo.insert(symbol.to_string(), bbo);
+        for listener in &mut self.listeners {
+            listener.on_bbo_change(symbol, bbo.0, bbo.1);
+        }
     }
     
-    fn validate_order(order: &Order) -> Result<(), String> {
+    fn validate_order(order: &Order) -> Result<(), OrderError> {
         if order.quantity == 0 {
-            return Err(format!(
-                "Invalid order: Quantity must be greater than 0; (symbol={}, side={:?}, price={:?}, qty={:?})",
-                order.symbol,
-                order.side,
-                order.price,
-                order.quantity
-            ));
+            return Err(OrderError::ZeroQuantity);
         }
     
         if order.symbol.is_empty() {
-            return Err(format!(
-                "Invalid order: Symbol cannot be empty; (symbol={}, side={:?}, price={:?}, qty={})",
-                order.symbol,
-                order.side,
-                order.price,
-                order.quantity
-            ));
+            return Err(OrderError::EmptySymbol);
         }
     
-        if order.order_type == OrderType::Limit {
+        if order.requires_price() {
             // If price ever becomes None since it's Some()
-            let price = order.price.ok_or_else(|| {
-                format!(
-                    "Invalid order: Limit order requires price; (symbol={}, side={:?}, price={:?}, qty={})",
-                    order.symbol,
-                    order.side,
-                    order.price,
-                    order.quantity
-                )
-            })?;
+            let price = order.price.ok_or(OrderError::MissingLimitPrice)?;
     
             if price == 0 {
-                return Err(format!(
-                    "Invalid order: Price must be greater than 0; (symbol={}, side={:?}, price={:?}, qty={})",
-                    order.symbol,
-                    order.side,
-                    order.price,
-                    order.quantity
-                ));
+                return Err(OrderError::ZeroPrice);
+            }
+        }
+
+        if matches!(order.order_type, OrderType::Stop | OrderType::StopLimit)
+            && order.trigger_price.unwrap_or(0) == 0
+        {
+            return Err(OrderError::MissingTriggerPrice);
+        }
+
+        if order.order_type == OrderType::TrailingStop
+            && !order.trailing_offset.is_some_and(|offset| offset.is_valid())
+        {
+            return Err(OrderError::InvalidTrailingOffset);
+        }
+
+        if order.peg.is_some_and(|peg| peg.limit == Some(0)) {
+            return Err(OrderError::ZeroPrice);
+        }
+
+        if order.post_only.is_some()
+            && (order.order_type != OrderType::Limit || order.time_in_force.is_immediate())
+        {
+            return Err(OrderError::InvalidPostOnly);
+        }
+
+        if let Some(display_quantity) = order.display_quantity {
+            if display_quantity == 0 || !order.requires_price() || order.hidden || order.all_or_none {
+                return Err(OrderError::InvalidDisplayQuantity);
+            }
+        }
+
+        if order.min_quantity.is_some_and(|min| min == 0 || min > order.quantity) {
+            return Err(OrderError::InvalidMinQuantity);
+        }
+
+        if let TimeInForce::GoodTillDate(expiry) = order.time_in_force {
+            if expiry <= Self::get_timestamp() {
+                return Err(OrderError::ExpiryInPast);
             }
         }
     
         Ok(())
     }
     
+    /// Make sure a post-only order can't take liquidity: reject it, or slide it
+    /// one tick (one price unit) passive of the opposite best price
+    fn apply_post_only(&self, order: &mut Order) -> Result<(), OrderError> {
+        let (mode, price) = match (order.post_only, order.price) {
+            (Some(mode), Some(price)) => (mode, price),
+            _ => return Ok(()),
+        };
+
+        let book = match self.order_books.get(&order.symbol) {
+            Some(book) => book,
+            None => return Ok(()),  // Nothing to cross
+        };
+
+        // Hidden liquidity counts too, otherwise a post-only order could hit it
+        let opposite_best = book.best_executable_price(order.side.opposite());
+        let slid_price = match (order.side, opposite_best) {
+            (Side::Buy, Some(ask)) if price >= ask => ask - 1,
+            (Side::Sell, Some(bid)) if price <= bid => bid + 1,
+            _ => return Ok(()),
+        };
+
+        match mode {
+            PostOnly::Slide if slid_price > 0 => {
+                order.price = Some(slid_price);
+                Ok(())
+            }
+            _ => Err(OrderError::WouldTakeLiquidity),
+        }
+    }
+
+    /// Price a pegged order would have against the current book
+    ///
+    /// Pegs follow the best displayed non-pegged prices, so they never track each other.
+    fn peg_price(&self, order: &Order) -> Option<Price> {
+        let book = self.order_books.get(&order.symbol)?;
+        order.peg?.price(
+            order.side,
+            book.reference_price(Side::Buy),
+            book.reference_price(Side::Sell),
+        )
+    }
+
+    /// Move resting pegged orders to their current pegged price
+    ///
+    /// A repriced order loses time priority and is matched again, since its new
+    /// price may cross the book. Matching can move the references again, so
+    /// this repeats until every peg is at its price. Executions of `report`'s
+    /// own order are merged into it; other pegs get one entry each in
+    /// `report.repriced`. Pegs whose reference has gone (e.g. one side of the
+    /// book emptied under a midpoint peg) stay at their last price.
+    fn reprice_pegs(&mut self, symbol: &str, report: &mut ExecutionReport) {
+        loop {
+            let pegged = match self.order_books.get(symbol) {
+                Some(book) => book.pegged_orders(),
+                None => return,
+            };
+            let mut moved = false;
+
+            for order_id in pegged {
+                // Earlier reprices may already have moved or filled this order
+                let target = match self.order_books.get(symbol).and_then(|book| book.get_order(order_id)) {
+                    Some(order) => match self.peg_price(order) {
+                        Some(price) if Some(price) != order.price => price,
+                        _ => continue,
+                    },
+                    None => continue,
+                };
+
+                let removed = self.order_books
+                    .get_mut(symbol)
+                    .and_then(|book| book.remove_order(order_id));
+                let Some(mut order) = removed else { continue };
+                order.price = Some(target);
+                order.timestamp = Self::get_timestamp();
+                moved = true;
+
+                let mut repriced = self.execute_order(order);
+                repriced.triggered = self.fire_triggers(symbol, &repriced.trades);
+                self.settle_orders(symbol, &mut repriced);
+
+                if order_id == report.order_id {
+                    report.merge(repriced);
+                } else if let Some(earlier) = report.repriced.iter_mut().find(|r| r.order_id == order_id) {
+                    earlier.merge(repriced);
+                } else {
+                    report.repriced.push(repriced);
+                }
+            }
+
+            if !moved {
+                return;
+            }
+        }
+    }
+
+    /// Reprice pegs after orders left the book without a new order arriving
+    /// (cancels, expiries); the repriced orders are settled but not reported
+    fn reprice_pegs_after_removal(&mut self, symbol: &str) {
+        let mut report = ExecutionReport::new(0, Self::get_timestamp());
+        self.reprice_pegs(symbol, &mut report);
+    }
+
     /// Init or retrieve book for symbol
     fn get_or_create_book(&mut self, symbol: &str) -> &mut OrderBook {
+        let market_data = self.market_data;
         self.order_books
             .entry(symbol.to_string())
-            .or_insert_with(OrderBook::new)
+            .or_insert_with(|| {
+                let mut book = OrderBook::new();
+                if market_data {
+                    book.enable_events();
+                }
+                book
+            })
+    }
+
+    /// Take the market data events recorded for a symbol since the last call
+    ///
+    /// Sequence numbers increase by one per event within a symbol. A snapshot
+    /// from `get_order_book(symbol)` plus the events after its `sequence()`
+    /// rebuilds the displayed book.
+    pub fn take_market_data(&mut self, symbol: &str) -> Vec<SequencedEvent> {
+        self.order_books
+            .get_mut(symbol)
+            .map(|book| book.take_events())
+            .unwrap_or_default()
     }
 
     /// Get read-only order book for a symbol
@@ -99,185 +538,695 @@ impl MatchingEngine {
         self.order_books.get(symbol)
     }
 
-    /// Process an incoming order
-    pub fn process_order(&mut self, mut order: Order) -> Vec<Trade> {
-        let mut trades = Vec::new();
+    /// Get read-only pending stop orders for a symbol
+    pub fn get_trigger_book(&self, symbol: &str) -> Option<&TriggerBook> {
+        self.trigger_books.get(symbol)
+    }
 
-        // Try to match the order
-        match order.side {
-            Side::Buy => self.match_buy_order(&mut order, &mut trades),
-            Side::Sell => self.match_sell_order(&mut order, &mut trades),
-        }
+    /// Price of the most recent trade in a symbol
+    pub fn last_trade_price(&self, symbol: &str) -> Option<Price> {
+        self.last_trade_prices.get(symbol).copied()
+    }
 
-        // If order has remaining quantity, add to book
-        if order.quantity > 0 {
-            let book = self.get_or_create_book(&order.symbol);
-            book.add_order(order);
+    /// Process an incoming order, then fire any stop orders its trades trigger
+    /// and reprice pegged orders to the new best bid/offer
+    pub fn process_order(&mut self, order: Order) -> ExecutionReport {
+        let symbol = order.symbol.clone();
+        let mut report = self.execute_order(order);
+        report.triggered = self.fire_triggers(&symbol, &report.trades);
+        self.settle_orders(&symbol, &mut report);
+        self.reprice_pegs(&symbol, &mut report);
+        self.notify_bbo(&symbol);
+        report
+    }
+
+    /// Link two prepared orders as an OCO pair and process them in turn
+    fn process_linked(&mut self, first: Order, second: Order) -> (ExecutionReport, ExecutionReport) {
+        self.order_groups.link_oco(first.id, second.id);
+        let first_report = self.process_order(first);
+
+        // The first leg already filled (or left the book): the second never goes live
+        if !self.order_groups.is_linked(second.id) {
+            let mut second_report = ExecutionReport::new(second.id, second.timestamp);
+            second_report.cancelled_quantity = second.quantity;
+            self.finish_record(second.id, OrderState::Cancelled);
+            return (first_report, second_report);
         }
 
-        trades
+        let second_report = self.process_order(second);
+        (first_report, second_report)
     }
 
-    /// Cancel an order by ID and symbol
-    pub fn cancel_order(&mut self, symbol: &str, order_id: OrderId) -> bool {
-        if let Some(book) = self.order_books.get_mut(symbol) {
-            book.cancel_order(order_id)
-        } else {
-            false  // Symbol not found
+    /// Key a client order ID is unique under (its owning account)
+    fn client_order_key(&self, order: &Order) -> Option<ClientOrderKey> {
+        let client_order_id = order.client_order_id.clone()?;
+        Some((order.owner.clone(), client_order_id))
+    }
+
+    /// Add a newly live order to the ID lookups
+    fn index_order(&mut self, order: &Order) {
+        let client_key = self.client_order_key(order);
+        if let Some(key) = &client_key {
+            self.client_order_ids.insert(key.clone(), order.id);
         }
+        self.order_index.insert(order.id, (order.symbol.clone(), client_key));
     }
 
-    fn match_buy_order(&mut self, order: &mut Order, trades: &mut Vec<Trade>) {
-        // Get price levels first (before we borrow the book)
-        let symbol = order.symbol.clone();
-        
-        let price_levels: Vec<Price> = {
-            let book = self.get_or_create_book(&symbol);
-            let asks = book.asks_mut();
-            
-            if let Some(buy_price) = order.price {
-                asks.range(..=buy_price)
-                    .map(|(price, _)| *price)
-                    .collect()
-            } else {
-                asks.keys()
-                    .copied()
-                    .collect()
-            }
-        }; // book borrow ends here
-    
-        // Now process without holding the book borrow
-        for ask_price in price_levels {
-            if order.quantity == 0 {
-                break;
+    /// Remove an order that is done from the ID lookups
+    fn unindex_order(&mut self, order_id: OrderId) {
+        if let Some((_, Some(client_key))) = self.order_index.remove(&order_id) {
+            self.client_order_ids.remove(&client_key);
+        }
+    }
+
+    /// Check if an order is resting on the book or waiting for its stop trigger
+    fn is_live(&self, symbol: &str, order_id: OrderId) -> bool {
+        self.order_books.get(symbol).is_some_and(|book| book.get_order(order_id).is_some())
+            || self.trigger_books.get(symbol).is_some_and(|triggers| triggers.get_order(order_id).is_some())
+    }
+
+    /// Apply order group rules to everything that happened in `report`, and
+    /// drop orders that are done from the order index
+    ///
+    /// Bracket entries that traded activate exits for what they filled, and
+    /// OCO legs that traded or are no longer live cancel their sibling.
+    fn settle_orders(&mut self, symbol: &str, report: &mut ExecutionReport) {
+        let mut fills = Vec::new();
+        let mut touched = Vec::new();
+        collect_order_activity(report, &mut fills, &mut touched);
+
+        let mut entry_fills: Vec<(OrderId, Quantity)> = Vec::new();
+        for &(order_id, price, quantity) in &fills {
+            if self.order_groups.is_bracket_entry(order_id) {
+                match entry_fills.iter_mut().find(|(id, _)| *id == order_id) {
+                    Some((_, filled)) => *filled += quantity,
+                    None => entry_fills.push((order_id, quantity)),
+                }
             }
-    
-            // Get asks for each iteration
-            let asks = {
-                let book = self.order_books
-                    .entry(symbol.clone())
-                    .or_insert_with(OrderBook::new);
-                book.asks_mut()
-            };            
-    
-            if let Some(ask_orders) = asks.get_mut(&ask_price) {
-                while order.quantity > 0 && !ask_orders.is_empty() {
-                    let mut ask_order = ask_orders.pop_front().unwrap();
-                    let trade_qty = order.quantity.min(ask_order.quantity);
-    
-                    // Retrieve timestamp
-                    let timestamp = MatchingEngine::get_timestamp();
-    
-                    let trade = Trade::new(
-                        order.id,
-                        ask_order.id,
-                        ask_price,
-                        trade_qty,
-                        timestamp,
-                    );
-                    trades.push(trade);
-    
-                    order.quantity -= trade_qty;
-                    ask_order.quantity -= trade_qty;
-    
-                    // Re-borrow to put order back if needed
-                    if ask_order.quantity > 0 {
-                        let book = self.get_or_create_book(&symbol);
-                        let asks = book.asks_mut();
-                        if let Some(orders) = asks.get_mut(&ask_price) {
-                            orders.push_front(ask_order);
-                        }
-                        break; // Exit inner loop since we put order back
+            if let Some(record) = self.order_records.get_mut(&order_id) {
+                record.filled_quantity += quantity;
+                record.notional += price as u128 * quantity as u128;
+
+                let leaves = record.quantity.saturating_sub(record.filled_quantity);
+                for listener in &mut self.listeners {
+                    if leaves == 0 {
+                        listener.on_fill(order_id, price, quantity);
+                    } else {
+                        listener.on_partial_fill(order_id, price, quantity, leaves);
                     }
                 }
-    
-                // Clean up empty price levels
-                let book = self.get_or_create_book(&symbol);
-                let asks = book.asks_mut();
-                if let Some(orders) = asks.get(&ask_price) {
-                    if orders.is_empty() {
-                        asks.remove(&ask_price);
-                    }
+            }
+        }
+
+        for (entry_id, quantity) in entry_fills {
+            self.activate_bracket_exits(entry_id, quantity, report);
+        }
+
+        for order_id in touched {
+            let traded = fills.iter().any(|&(id, _, _)| id == order_id);
+            if !traded && self.is_live(symbol, order_id) {
+                continue;
+            }
+
+            if let Some(sibling) = self.order_groups.take_sibling(order_id) {
+                if self.cancel_order(symbol, sibling) {
+                    report.cancelled_orders.push(sibling);
                 }
             }
+
+            if self.is_live(symbol, order_id) {
+                continue;  // Bracket entry still working
+            }
+            self.unindex_order(order_id);
+            self.finish_record(order_id, OrderState::Cancelled);
+            self.order_groups.remove_bracket(order_id);
+            self.order_groups.remove_exit(order_id);
         }
     }
-    
-    fn match_sell_order(&mut self, order: &mut Order, trades: &mut Vec<Trade>) {
-        // Get price levels first (before we borrow the book)
-        let symbol = order.symbol.clone();
-        
-        let price_levels: Vec<Price> = {
-            let book = self.get_or_create_book(&symbol);
-            let bids = book.bids_mut();
-            
-            if let Some(sell_price) = order.price {
-                bids.range(sell_price..)
-                    .map(|(price, _)| *price)
-                    .rev()
-                    .collect()
-            } else {
-                bids.keys()
-                    .copied()
-                    .rev()
-                    .collect()
+
+    /// Submit a bracket entry's exit pair for `quantity` it just filled
+    fn activate_bracket_exits(&mut self, entry_id: OrderId, quantity: Quantity, report: &mut ExecutionReport) {
+        let Some((take_profit, stop_loss)) = self.order_groups.bracket_exits(entry_id, quantity) else {
+            return;
+        };
+
+        // Exits were validated with the entry, so this only fails if
+        // e.g. a post-only or peg check can't be met right now
+        match (self.prepare_order(take_profit), self.prepare_order(stop_loss)) {
+            (Ok(take_profit), Ok(stop_loss)) => {
+                self.order_groups.add_exit(take_profit.id, entry_id);
+                self.order_groups.add_exit(stop_loss.id, entry_id);
+                let (take_profit, stop_loss) = self.process_linked(take_profit, stop_loss);
+                report.triggered.push(take_profit);
+                report.triggered.push(stop_loss);
             }
-        }; // book borrow ends here
-    
-        // Now process without holding the book borrow
-        for bid_price in price_levels {
-            if order.quantity == 0 {
-                break;
+            (Ok(exit), Err(e)) | (Err(e), Ok(exit)) => {
+                self.finish_record(exit.id, OrderState::Rejected);
+                self.notify_reject(&exit, &e);
             }
-    
-            // Get bids for each iteration
-            let bids = {
-                let book = self.order_books
-                    .entry(symbol.clone())
-                    .or_insert_with(OrderBook::new);
-                book.bids_mut()
+            (Err(_), Err(_)) => {}
+        }
+    }
+
+    /// Record that an order is done: Filled if its whole quantity traded,
+    /// otherwise `state`. Orders already in a terminal state are left alone.
+    ///
+    /// Listeners hear about cancels and expiries here (fills are reported as
+    /// they settle, rejections where the error is known).
+    fn finish_record(&mut self, order_id: OrderId, state: OrderState) {
+        let record = match self.order_records.get_mut(&order_id) {
+            Some(record) if !record.state.is_terminal() => record,
+            _ => return,
+        };
+        record.state = if record.filled_quantity >= record.quantity {
+            OrderState::Filled
+        } else {
+            state
+        };
+
+        let unfilled = record.quantity - record.filled_quantity;
+        for listener in &mut self.listeners {
+            match record.state {
+                OrderState::Cancelled => listener.on_cancel(order_id, unfilled),
+                OrderState::Expired => listener.on_expire(order_id, unfilled),
+                _ => {}
+            }
+        }
+
+        // Forget the oldest finished orders beyond the retention bound
+        self.finished_orders.push_back(order_id);
+        while self.finished_orders.len() > self.status_retention {
+            if let Some(oldest) = self.finished_orders.pop_front() {
+                self.order_records.remove(&oldest);
+            }
+        }
+    }
+
+    /// Current state of an order, live or finished
+    ///
+    /// Live orders report their open quantity, price level and queue position;
+    /// finished orders report how they ended. Only the most recent finished
+    /// orders are kept (see `with_status_retention`); older ones return None.
+    pub fn order_status(&self, order_id: OrderId) -> Option<OrderStatus> {
+        let record = self.order_records.get(&order_id)?;
+        let book = self.order_books.get(&record.symbol);
+        let live = self.get_order(order_id);
+
+        let state = match (live, record.filled_quantity) {
+            (Some(_), 0) => OrderState::New,
+            (Some(_), _) => OrderState::PartiallyFilled,
+            (None, _) => record.state,
+        };
+        let resting = book.and_then(|book| book.get_order(order_id));
+
+        Some(OrderStatus {
+            order_id,
+            symbol: record.symbol.clone(),
+            side: record.side,
+            state,
+            original_quantity: record.original_quantity,
+            remaining_quantity: live.map_or(0, |order| order.leaves_quantity()),
+            filled_quantity: record.filled_quantity,
+            average_fill_price: (record.filled_quantity > 0)
+                .then(|| record.notional as f64 / record.filled_quantity as f64),
+            price: resting.and_then(|order| order.price),
+            queue_position: book.and_then(|book| book.queue_position(order_id)),
+        })
+    }
+
+    /// Fire stops reached by `trades`, checking every print in order, including
+    /// cascades where a triggered order's own trades reach further stops
+    ///
+    /// With no trades (e.g. a stop was just parked) the last trade price is checked.
+    fn fire_triggers(&mut self, symbol: &str, trades: &[Trade]) -> Vec<ExecutionReport> {
+        let mut reports = Vec::new();
+
+        // Nothing fires until the symbol has traded
+        let mut prices: Vec<Price> = if trades.is_empty() {
+            self.last_trade_price(symbol).into_iter().collect()
+        } else {
+            trades.iter().map(|trade| trade.price).collect()
+        };
+
+        while !prices.is_empty() {
+            let triggers = match self.trigger_books.get_mut(symbol) {
+                Some(triggers) => triggers,
+                None => break,
             };
-    
-            if let Some(bid_orders) = bids.get_mut(&bid_price) {
-                while order.quantity > 0 && !bid_orders.is_empty() {
-                    let mut bid_order = bid_orders.pop_front().unwrap();
-                    let trade_qty = order.quantity.min(bid_order.quantity);
-    
-                    // Retrieve timestamp
-                    let timestamp = MatchingEngine::get_timestamp();
-    
-                    let trade = Trade::new(
-                        bid_order.id,
-                        order.id,
-                        bid_price,
-                        trade_qty,
-                        timestamp,
-                    );
-                    trades.push(trade);
-    
-                    order.quantity -= trade_qty;
-                    bid_order.quantity -= trade_qty;
-    
-                    // Re-borrow to put order back if needed
-                    if bid_order.quantity > 0 {
-                        let book = self.get_or_create_book(&symbol);
-                        let bids = book.bids_mut();
-                        if let Some(orders) = bids.get_mut(&bid_price) {
-                            orders.push_front(bid_order);
+
+            // Move trailing stops with each print before checking for reversals,
+            // so highs and lows inside a sweep count
+            let mut triggered = Vec::new();
+            for price in prices.drain(..) {
+                triggers.ratchet_trailing_stops(price);
+                triggered.extend(triggers.take_triggered(price));
+            }
+
+            for mut order in triggered {
+                order.order_type = match order.order_type {
+                    OrderType::StopLimit => OrderType::Limit,
+                    _ => OrderType::Market,
+                };
+                order.timestamp = Self::get_timestamp();
+                let report = self.execute_order(order);
+                prices.extend(report.trades.iter().map(|trade| trade.price));
+                reports.push(report);
+            }
+        }
+
+        reports
+    }
+
+    /// Match an order and rest or cancel its remainder
+    fn execute_order(&mut self, mut order: Order) -> ExecutionReport {
+        let mut report = ExecutionReport::new(order.id, order.timestamp);
+
+        // Stops wait off the visible book until their trigger price trades
+        if order.is_stop() {
+            report.leaves_quantity = order.quantity;
+            report.rested = true;
+            self.index_order(&order);
+            self.trigger_books
+                .entry(order.symbol.clone())
+                .or_default()
+                .add_order(order);
+            return report;
+        }
+
+        // Fill-or-Kill, all-or-none and minimum quantity: check liquidity up front so
+        // the book is never touched when the order can't trade enough
+        let blocked = order.required_fill_quantity() > 0
+            && self.order_books
+                .get(&order.symbol)
+                .map_or(0, |book| {
+                    book.fillable_quantity(&order, |resting| self.order_groups.in_same_group(order.id, resting.id))
+                }) < order.required_fill_quantity();
+
+        // Try to match the order
+        if !blocked {
+            self.match_order(&mut order, &mut report);
+        }
+        report.filled_quantity = report.trades.iter().map(|t| t.quantity).sum();
+
+        if let Some(last_trade) = report.trades.last() {
+            self.last_trade_prices.insert(order.symbol.clone(), last_trade.price);
+        }
+
+        // Market-to-limit: remainder becomes a limit order at the last fill price
+        if order.order_type == OrderType::Market
+            && order.quantity > 0
+            && self.market_order_policy == MarketOrderPolicy::ConvertToLimit
+        {
+            if let Some(last_trade) = report.trades.last() {
+                order.order_type = OrderType::Limit;
+                order.price = Some(last_trade.price);
+            }
+        }
+
+        // Rest any remainder; IOC/FOK and unconverted market remainders are cancelled
+        if order.quantity > 0 {
+            if order.time_in_force.is_immediate() || order.order_type == OrderType::Market {
+                report.cancelled_quantity += order.quantity;
+            } else {
+                report.leaves_quantity = order.quantity;
+                report.rested = true;
+                self.index_order(&order);
+                let book = self.get_or_create_book(&order.symbol);
+                book.add_order(order);
+            }
+        }
+
+        report
+    }
+
+    /// Cancel an order (resting or pending stop) by ID and symbol
+    ///
+    /// Pegged orders are repriced if the cancel moved the best bid/offer, and
+    /// the other leg of an OCO pair is cancelled too.
+    pub fn cancel_order(&mut self, symbol: &str, order_id: OrderId) -> bool {
+        let cancelled = self.order_books
+            .get_mut(symbol)
+            .is_some_and(|book| book.cancel_order(order_id));
+
+        if cancelled {
+            self.reprice_pegs_after_removal(symbol);
+        }
+
+        let cancelled = cancelled || self.trigger_books
+            .get_mut(symbol)
+            .is_some_and(|triggers| triggers.remove_order(order_id).is_some());
+
+        if cancelled {
+            self.finish_record(order_id, OrderState::Cancelled);
+            let mut report = ExecutionReport::new(order_id, Self::get_timestamp());
+            report.cancelled_orders.push(order_id);
+            self.settle_orders(symbol, &mut report);
+            self.notify_bbo(symbol);
+        }
+
+        cancelled
+    }
+
+    /// Symbol of a live (resting or pending stop) order
+    pub fn order_symbol(&self, order_id: OrderId) -> Option<&Symbol> {
+        self.order_index.get(&order_id).map(|(symbol, _)| symbol)
+    }
+
+    /// Get a live (resting or pending stop) order by ID alone
+    pub fn get_order(&self, order_id: OrderId) -> Option<&Order> {
+        let symbol = self.order_symbol(order_id)?;
+        self.order_books
+            .get(symbol)
+            .and_then(|book| book.get_order(order_id))
+            .or_else(|| self.trigger_books.get(symbol)?.get_order(order_id))
+    }
+
+    /// Cancel an order by ID alone, wherever it rests
+    pub fn cancel_order_by_id(&mut self, order_id: OrderId) -> bool {
+        match self.order_symbol(order_id).cloned() {
+            Some(symbol) => self.cancel_order(&symbol, order_id),
+            None => false,
+        }
+    }
+
+    /// Amend a resting order by ID alone (see `modify_order`)
+    pub fn modify_order_by_id(
+        &mut self,
+        order_id: OrderId,
+        new_price: Price,
+        new_quantity: Quantity,
+    ) -> Result<ExecutionReport, OrderError> {
+        let Some(symbol) = self.order_symbol(order_id).cloned() else {
+            let error = OrderError::OrderNotFound(order_id);
+            self.notify_amend_reject(order_id, &error);
+            return Err(error);
+        };
+        self.modify_order(&symbol, order_id, new_price, new_quantity)
+    }
+
+    /// Engine order ID of a live order from its owner's client order ID
+    pub fn order_id_for_client_id(&self, owner: Option<&str>, client_order_id: &str) -> Option<OrderId> {
+        let key = (owner.map(str::to_string), client_order_id.to_string());
+        self.client_order_ids.get(&key).copied()
+    }
+
+    /// Cancel a live order by its owner's client order ID
+    pub fn cancel_order_by_client_id(&mut self, owner: Option<&str>, client_order_id: &str) -> bool {
+        match self.order_id_for_client_id(owner, client_order_id) {
+            Some(order_id) => self.cancel_order_by_id(order_id),
+            None => false,
+        }
+    }
+
+    /// Amend a live order by its owner's client order ID (see `modify_order`)
+    pub fn modify_order_by_client_id(
+        &mut self,
+        owner: Option<&str>,
+        client_order_id: &str,
+        new_price: Price,
+        new_quantity: Quantity,
+    ) -> Result<ExecutionReport, OrderError> {
+        let Some(order_id) = self.order_id_for_client_id(owner, client_order_id) else {
+            let error = OrderError::ClientOrderIdNotFound(client_order_id.to_string());
+            self.notify_amend_reject(0, &error);
+            return Err(error);
+        };
+        self.modify_order_by_id(order_id, new_price, new_quantity)
+    }
+
+    /// Cancel every resting order matching `filter` in one pass, returning their IDs
+    ///
+    /// IDs come back in symbol order, then bids before asks, in price and queue
+    /// order. Only orders on the book are cancelled; pending stops are left alone.
+    /// Pegged orders and order groups are updated as for `cancel_order`.
+    pub fn mass_cancel(&mut self, filter: &MassCancelFilter) -> Vec<OrderId> {
+        let mut cancelled = Vec::new();
+
+        // Walk books in symbol order so results and callbacks replay identically
+        let mut books: Vec<(&Symbol, &mut OrderBook)> = self.order_books
+            .iter_mut()
+            .filter(|(symbol, _)| filter.symbol.as_ref().is_none_or(|s| s == *symbol))
+            .collect();
+        books.sort_by_key(|(symbol, _)| *symbol);
+        for (symbol, book) in books {
+            let removed = book.remove_where(|o| filter.matches(o));
+            cancelled.extend(removed.into_iter().map(|o| (symbol.clone(), o.id)));
+        }
+
+        let mut symbols: Vec<Symbol> = cancelled.iter().map(|(symbol, _)| symbol.clone()).collect();
+        symbols.sort();
+        symbols.dedup();
+        for symbol in &symbols {
+            self.reprice_pegs_after_removal(symbol);
+        }
+
+        for (symbol, order_id) in &cancelled {
+            self.finish_record(*order_id, OrderState::Cancelled);
+            let mut report = ExecutionReport::new(*order_id, Self::get_timestamp());
+            report.cancelled_orders.push(*order_id);
+            self.settle_orders(symbol, &mut report);
+        }
+        for symbol in &symbols {
+            self.notify_bbo(symbol);
+        }
+
+        cancelled.into_iter().map(|(_, order_id)| order_id).collect()
+    }
+
+    /// Remove every resting or pending stop order that has expired at time `now`
+    ///
+    /// Orders come back in symbol order, resting orders before pending stops.
+    pub fn expire_orders(&mut self, now: u64) -> Vec<Order> {
+        let resting: Vec<Order> = self.order_books
+            .values_mut()
+            .flat_map(|book| book.expire_orders(now))
+            .collect();
+        let pending = self.trigger_books
+            .values_mut()
+            .flat_map(|triggers| triggers.expire_orders(now));
+        let mut expired: Vec<Order> = resting.into_iter().chain(pending).collect();
+
+        // Books are keyed by a HashMap; settle in symbol order so callbacks replay identically
+        expired.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+
+        // Expiries may have moved the best bid/offer under pegged orders
+        let mut symbols: Vec<Symbol> = expired.iter().map(|o| o.symbol.clone()).collect();
+        symbols.sort();
+        symbols.dedup();
+        for symbol in &symbols {
+            self.reprice_pegs_after_removal(symbol);
+        }
+
+        // Expired orders take their OCO sibling with them
+        for order in &expired {
+            self.finish_record(order.id, OrderState::Expired);
+            let mut report = ExecutionReport::new(order.id, Self::get_timestamp());
+            report.cancelled_orders.push(order.id);
+            self.settle_orders(&order.symbol, &mut report);
+        }
+        for symbol in &symbols {
+            self.notify_bbo(symbol);
+        }
+
+        expired
+    }
+
+    /// Amend a resting order's price and/or quantity (cancel/replace)
+    ///
+    /// Reducing quantity at the same price keeps the order's queue position.
+    /// Any price change or quantity increase loses priority and re-runs matching,
+    /// since the new price may cross the book. The order keeps its ID.
+    /// For a pegged order `new_price` replaces its limit cap.
+    pub fn modify_order(
+        &mut self,
+        symbol: &str,
+        order_id: OrderId,
+        new_price: Price,
+        new_quantity: Quantity,
+    ) -> Result<ExecutionReport, OrderError> {
+        let result = self.amend_order(symbol, order_id, new_price, new_quantity);
+        if let Err(error) = &result {
+            self.notify_amend_reject(order_id, error);
+        }
+        result
+    }
+
+    fn amend_order(
+        &mut self,
+        symbol: &str,
+        order_id: OrderId,
+        new_price: Price,
+        new_quantity: Quantity,
+    ) -> Result<ExecutionReport, OrderError> {
+        if new_quantity == 0 {
+            return Err(OrderError::ZeroQuantity);
+        }
+
+        if new_price == 0 {
+            return Err(OrderError::ZeroPrice);
+        }
+
+        let current = self.order_books
+            .get(symbol)
+            .and_then(|book| book.get_order(order_id))
+            .ok_or(OrderError::OrderNotFound(order_id))?
+            .clone();
+
+        let mut amended = current.clone();
+        amended.price = Some(new_price);
+        amended.quantity = new_quantity;
+        amended.reserve_quantity = 0;
+
+        // For pegged orders the new price is the peg's limit cap
+        if let Some(peg) = amended.peg.as_mut() {
+            peg.limit = Some(new_price);
+            amended.price = self.peg_price(&amended).or(current.price);
+        }
+
+        // Post-only orders can't be amended into taking liquidity either
+        self.apply_post_only(&mut amended)?;
+
+        if let Some(record) = self.order_records.get_mut(&order_id) {
+            record.quantity = record.filled_quantity + new_quantity;
+        }
+
+        let book = self.get_or_create_book(symbol);
+
+        if amended.price == current.price && new_quantity <= current.leaves_quantity() {
+            // Same price, same or smaller size: amend in place and keep priority
+            if new_quantity < current.leaves_quantity() {
+                book.reduce_order(order_id, new_quantity);
+            }
+            let mut report = ExecutionReport::new(order_id, current.timestamp);
+            report.leaves_quantity = new_quantity;
+            report.rested = true;
+            return Ok(report);
+        }
+
+        // Price change or size increase: lose priority and re-match at the new terms
+        book.remove_order(order_id);
+        amended.timestamp = Self::get_timestamp();
+
+        Ok(self.process_order(amended))
+    }
+
+    /// Match an incoming order against the opposite side of its book
+    fn match_order(&mut self, order: &mut Order, report: &mut ExecutionReport) {
+        let book = match self.order_books.get_mut(&order.symbol) {
+            Some(book) => book,
+            None => return,  // No resting orders for this symbol yet
+        };
+        let resting_side = order.side.opposite();
+        let order_id = order.id;
+        let groups = &self.order_groups;
+
+        // Walk crossing price levels best first
+        for price in book.crossing_prices(order) {
+            while order.quantity > 0 {
+                // All-or-none orders we can't fill completely, and orders in our own
+                // OCO pair or bracket, keep their place and are skipped
+                let in_group = |resting: &Order| groups.in_same_group(order_id, resting.id);
+                let (resting_id, resting_quantity, same_owner) = match book.next_match(order, price, in_group) {
+                    Some(resting) => (resting.id, resting.leaves_quantity(), order.is_same_owner(resting)),
+                    None => break,  // Level exhausted
+                };
+
+                // Self-trade prevention: resolve before filling against our own order
+                if let Some(mode) = order.self_trade_prevention {
+                    if same_owner {
+                        match mode {
+                            SelfTradePrevention::CancelNewest => {
+                                report.cancelled_quantity += order.quantity;
+                                order.quantity = 0;
+                            }
+                            SelfTradePrevention::CancelOldest => {
+                                book.remove_order(resting_id);
+                                report.cancelled_orders.push(resting_id);
+                            }
+                            SelfTradePrevention::CancelBoth => {
+                                book.remove_order(resting_id);
+                                report.cancelled_orders.push(resting_id);
+                                report.cancelled_quantity += order.quantity;
+                                order.quantity = 0;
+                            }
+                            SelfTradePrevention::DecrementAndCancel => {
+                                // Shrink both sides by the overlap; whichever hits zero is cancelled.
+                                // A side that survives now has less to fill, so its record shrinks too
+                                let overlap = order.quantity.min(resting_quantity);
+                                if overlap == resting_quantity {
+                                    book.remove_order(resting_id);
+                                    report.cancelled_orders.push(resting_id);
+                                } else {
+                                    book.reduce_order(resting_id, resting_quantity - overlap);
+                                    if let Some(record) = self.order_records.get_mut(&resting_id) {
+                                        record.quantity -= overlap;
+                                    }
+                                    for listener in &mut self.listeners {
+                                        listener.on_cancel(resting_id, overlap);
+                                    }
+                                }
+                                if overlap < order.quantity {
+                                    if let Some(record) = self.order_records.get_mut(&order.id) {
+                                        record.quantity -= overlap;
+                                    }
+                                    for listener in &mut self.listeners {
+                                        listener.on_cancel(order.id, overlap);
+                                    }
+                                }
+                                report.cancelled_quantity += overlap;
+                                order.quantity -= overlap;
+                            }
                         }
-                        break; // Exit inner loop since we put order back
+                        continue;
                     }
                 }
-    
-                // Clean up empty price levels
-                let book = self.get_or_create_book(&symbol);
-                let bids = book.bids_mut();
-                if let Some(orders) = bids.get(&bid_price) {
-                    if orders.is_empty() {
-                        bids.remove(&bid_price);
-                    }
+
+                let fill = match book.fill_order(resting_side, price, resting_id, order.quantity) {
+                    Some(fill) => fill,
+                    None => break,
+                };
+
+                let trade = Trade::new(
+                    self.next_trade_id,
+                    order,
+                    fill.order_id,
+                    price,
+                    fill.quantity,
+                    Self::get_timestamp(),
+                );
+                book.print_trade(&trade);
+                for listener in &mut self.listeners {
+                    listener.on_trade(&trade);
                 }
+                report.trades.push(trade);
+                self.next_trade_id += 1;
+
+                order.quantity -= fill.quantity;
+            }
+
+            if order.quantity == 0 {
+                break;
             }
         }
     }
-}
\ No newline at end of file
+}
+
+/// Gather fills per order and every order whose state a report may have
+/// changed, including stops it triggered
+fn collect_order_activity(report: &ExecutionReport, fills: &mut Vec<(OrderId, Price, Quantity)>, touched: &mut Vec<OrderId>) {
+    touched.push(report.order_id);
+    touched.extend(&report.cancelled_orders);
+
+    for trade in &report.trades {
+        for order_id in [trade.buyer_order_id, trade.seller_order_id] {
+            fills.push((order_id, trade.price, trade.quantity));
+            touched.push(order_id);
+        }
+    }
+
+    // Repriced pegs are settled by `reprice_pegs`; bracket exits are added to
+    // `triggered` only after this runs
+    for nested in &report.triggered {
+        collect_order_activity(nested, fills, touched);
+    }
+}
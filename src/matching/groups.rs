@@ -0,0 +1,82 @@
+use crate::types::{Order, OrderId, Quantity};
+use std::collections::HashMap;
+
+/// Exit templates for a bracket entry that is still working
+struct BracketExits {
+    take_profit: Order,
+    stop_loss: Order,
+}
+
+/// Order group membership tracked by the engine
+///
+/// One-cancels-other legs are linked to each other both ways. Bracket exit
+/// templates (without IDs) are held here while their entry order is working,
+/// and live exits remember their entry. Orders in the same group never trade
+/// with each other.
+#[derive(Default)]
+pub(crate) struct OrderGroups {
+    oco_links: HashMap<OrderId, OrderId>,
+    brackets: HashMap<OrderId, BracketExits>,
+    exit_entries: HashMap<OrderId, OrderId>,  // Live bracket exit -> its entry
+}
+
+impl OrderGroups {
+    /// Link two orders so that either one filling or leaving the book cancels the other
+    pub fn link_oco(&mut self, first: OrderId, second: OrderId) {
+        self.oco_links.insert(first, second);
+        self.oco_links.insert(second, first);
+    }
+
+    /// Check if an order is still linked to an OCO sibling
+    pub fn is_linked(&self, order_id: OrderId) -> bool {
+        self.oco_links.contains_key(&order_id)
+    }
+
+    /// Check if two orders belong to the same OCO pair or bracket
+    pub fn in_same_group(&self, first: OrderId, second: OrderId) -> bool {
+        self.oco_links.get(&first) == Some(&second)
+            || self.exit_entries.get(&first) == Some(&second)
+            || self.exit_entries.get(&second) == Some(&first)
+    }
+
+    /// Unlink an OCO pair, returning the other leg
+    pub fn take_sibling(&mut self, order_id: OrderId) -> Option<OrderId> {
+        let sibling = self.oco_links.remove(&order_id)?;
+        self.oco_links.remove(&sibling);
+        Some(sibling)
+    }
+
+    /// Hold bracket exit templates while `entry_id` is working
+    pub fn add_bracket(&mut self, entry_id: OrderId, take_profit: Order, stop_loss: Order) {
+        self.brackets.insert(entry_id, BracketExits { take_profit, stop_loss });
+    }
+
+    /// Check if an order is a working bracket entry
+    pub fn is_bracket_entry(&self, order_id: OrderId) -> bool {
+        self.brackets.contains_key(&order_id)
+    }
+
+    /// Exits covering `quantity` of a bracket entry's fills
+    pub fn bracket_exits(&self, entry_id: OrderId, quantity: Quantity) -> Option<(Order, Order)> {
+        let exits = self.brackets.get(&entry_id)?;
+        let (mut take_profit, mut stop_loss) = (exits.take_profit.clone(), exits.stop_loss.clone());
+        take_profit.quantity = quantity;
+        stop_loss.quantity = quantity;
+        Some((take_profit, stop_loss))
+    }
+
+    /// Remember which entry a live bracket exit belongs to
+    pub fn add_exit(&mut self, exit_id: OrderId, entry_id: OrderId) {
+        self.exit_entries.insert(exit_id, entry_id);
+    }
+
+    /// Forget a finished bracket exit
+    pub fn remove_exit(&mut self, exit_id: OrderId) {
+        self.exit_entries.remove(&exit_id);
+    }
+
+    /// Drop a finished entry's exit templates
+    pub fn remove_bracket(&mut self, entry_id: OrderId) {
+        self.brackets.remove(&entry_id);
+    }
+}
@@ -0,0 +1,44 @@
+use crate::types::{Order, OrderError, OrderId, Price, Quantity, Trade};
+
+/// Observer for everything the engine does to orders and books
+///
+/// Register with `MatchingEngine::add_listener`. Every method defaults to a
+/// no-op, so listeners only implement what they care about. Callbacks run
+/// synchronously on the engine's thread, in registration order.
+///
+/// Within a request, trades are reported as they match; fills, cancels and
+/// expiries once the request has settled; and BBO changes last.
+pub trait EngineListener: Send {
+    /// Order passed validation and was assigned its ID, before it matches
+    fn on_accept(&mut self, _order: &Order) {}
+
+    /// Order was rejected; its ID is 0 unless it had already been accepted
+    /// (e.g. the other leg of an OCO pair failed)
+    fn on_reject(&mut self, _order: &Order, _reason: &OrderError) {}
+
+    /// Amendment of a live order was rejected and the order left as it was;
+    /// `order_id` is 0 when a client order ID didn't resolve to an order
+    fn on_amend_reject(&mut self, _order_id: OrderId, _reason: &OrderError) {}
+
+    /// Fill that completed an order
+    fn on_fill(&mut self, _order_id: OrderId, _price: Price, _quantity: Quantity) {}
+
+    /// Fill that left `leaves_quantity` of an order open
+    fn on_partial_fill(&mut self, _order_id: OrderId, _price: Price, _quantity: Quantity, _leaves_quantity: Quantity) {}
+
+    /// Order's unfilled quantity was cancelled, whether by request or by the
+    /// engine (IOC/market remainder, self-trade prevention, OCO sibling)
+    ///
+    /// A self-trade decrement that leaves the order live also lands here, for
+    /// the decremented quantity only; the order may be cancelled again later.
+    fn on_cancel(&mut self, _order_id: OrderId, _cancelled_quantity: Quantity) {}
+
+    /// Order's unfilled quantity expired
+    fn on_expire(&mut self, _order_id: OrderId, _expired_quantity: Quantity) {}
+
+    /// Trade executed (reported once, not per side)
+    fn on_trade(&mut self, _trade: &Trade) {}
+
+    /// Best displayed bid and/or ask of a symbol changed
+    fn on_bbo_change(&mut self, _symbol: &str, _best_bid: Option<Price>, _best_ask: Option<Price>) {}
+}
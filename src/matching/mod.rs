@@ -1,3 +1,6 @@
 mod engine;
+mod groups;
+mod listener;
 
-pub use engine::MatchingEngine;
\ No newline at end of file
+pub use engine::{MarketOrderPolicy, MassCancelFilter, MatchingEngine};
+pub use listener::EngineListener;
\ No newline at end of file
@@ -1,16 +1,39 @@
-use std::collections::{BTreeMap, VecDeque, HashMap};
-use crate::types::{Order, OrderId, Price, Side};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use crate::types::{Order, OrderId, Price, Quantity, SelfTradePrevention, Side, Trade};
+use super::{BookEvent, BookOrder, Depth, DepthLevel, PriceLevel, SequencedEvent};
+
+/// Quantity taken from a resting order during matching
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fill {
+    pub order_id: OrderId,
+    pub quantity: Quantity,
+}
 
 /// Order book maintaining bid and ask orders
 pub struct OrderBook {
     /// Buy orders (bids), sorted by price descending
-    bids: BTreeMap<Price, VecDeque<Order>>,
+    bids: BTreeMap<Price, PriceLevel>,
     
     /// Sell orders (asks), sorted by price ascending
-    asks: BTreeMap<Price, VecDeque<Order>>,
+    asks: BTreeMap<Price, PriceLevel>,
 
     /// Store order locations for O(1) lookup by Order ID
     order_locations: HashMap<OrderId, (Side, Price)>,
+
+    /// Resting pegged orders, so repricing doesn't walk the whole book
+    pegged: HashSet<OrderId>,
+
+    /// Sequence number of the last recorded event
+    sequence: u64,
+
+    /// Recorded market data events not yet taken (None while recording is off)
+    events: Option<Vec<SequencedEvent>>,
+}
+
+impl Default for OrderBook {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl OrderBook {
@@ -19,10 +42,49 @@ impl OrderBook {
             bids: BTreeMap::new(),
             asks: BTreeMap::new(),
             order_locations: HashMap::new(),
+            pegged: HashSet::new(),
+            sequence: 0,
+            events: None,
+        }
+    }
+
+    /// Start recording market data events for every change to the displayed book
+    pub fn enable_events(&mut self) {
+        self.events.get_or_insert_with(Vec::new);
+    }
+
+    /// Sequence number of the last recorded event (0 if none yet)
+    ///
+    /// A snapshot taken via `orders()` is current as of this sequence number;
+    /// applying later events to it rebuilds the displayed book.
+    pub fn sequence(&self) -> u64 {
+        self.sequence
+    }
+
+    /// Take all events recorded since the last call, oldest first
+    pub fn take_events(&mut self) -> Vec<SequencedEvent> {
+        self.events.as_mut().map(std::mem::take).unwrap_or_default()
+    }
+
+    /// Record a trade print for a trade in this book
+    pub fn print_trade(&mut self, trade: &Trade) {
+        self.emit(BookEvent::TradePrinted {
+            trade_id: trade.trade_id,
+            price: trade.price,
+            quantity: trade.quantity,
+            aggressor_side: trade.aggressor_side,
+        });
+    }
+
+    /// Record an event if recording is on
+    fn emit(&mut self, event: BookEvent) {
+        if let Some(events) = self.events.as_mut() {
+            self.sequence += 1;
+            events.push(SequencedEvent { sequence: self.sequence, event });
         }
     }
 
-    pub fn add_order(&mut self, order: Order) {
+    pub fn add_order(&mut self, mut order: Order) {
         let price = match order.price {
             Some(p) => p,
             None => return,
@@ -30,26 +92,51 @@ impl OrderBook {
     
         let side = order.side;
         let order_id = order.id;  // capture ID before moving order
+        if order.peg.is_some() {
+            self.pegged.insert(order_id);
+        }
+
+        // Icebergs only show their peak; the rest sits in reserve
+        if let Some(display) = order.display_quantity {
+            if order.quantity > display {
+                order.reserve_quantity += order.quantity - display;
+                order.quantity = display;
+            }
+        }
     
         let book = match side {
             Side::Buy => &mut self.bids,
             Side::Sell => &mut self.asks,
         };
+
+        let added = (!order.hidden).then_some(BookEvent::OrderAdded {
+            order_id,
+            side,
+            price,
+            quantity: order.quantity,
+        });
     
         book.entry(price)
-            .or_insert_with(VecDeque::new)
+            .or_default()
             .push_back(order);
         
         // Track order location
         self.order_locations.insert(order_id, (side, price));
+
+        if let Some(event) = added {
+            self.emit(event);
+        }
     }
 
     pub fn cancel_order(&mut self, order_id: OrderId) -> bool {
+        self.remove_order(order_id).is_some()
+    }
+
+    /// Remove an order from the book, returning it if it was resting
+    pub fn remove_order(&mut self, order_id: OrderId) -> Option<Order> {
         // Look up order location
-        let (side, price) = match self.order_locations.remove(&order_id) {
-            Some(loc) => loc,
-            None => return false, // Order not found
-        };
+        let (side, price) = self.order_locations.remove(&order_id)?;
+        self.pegged.remove(&order_id);
     
         // Get the appropriate book
         let book = match side {
@@ -58,62 +145,394 @@ impl OrderBook {
         };
     
         // Get orders at this price level
-        if let Some(orders) = book.get_mut(&price) {
-            // Find and remove the order
-            if let Some(pos) = orders.iter().position(|o| o.id == order_id) {
-                orders.remove(pos);
-                
-                // Remove price level if empty
-                if orders.is_empty() {
-                    book.remove(&price);
-                }
+        let orders = book.get_mut(&price)?;
+
+        // Find and remove the order
+        let order = orders.remove(order_id).map(restore_reserve);
                 
-                return true;
-            }
+        // Remove price level if empty
+        if orders.is_empty() {
+            book.remove(&price);
+        }
+
+        if order.as_ref().is_some_and(|o| !o.hidden) {
+            self.emit(BookEvent::OrderDeleted { order_id });
         }
     
-        false
+        order
     }
 
-    /// Get the highest buy price
+    /// Reduce a resting order's total quantity in place, keeping its queue position
+    ///
+    /// For icebergs the hidden reserve is reduced before the displayed peak.
+    pub fn reduce_order(&mut self, order_id: OrderId, new_quantity: Quantity) -> bool {
+        let (side, price) = match self.order_locations.get(&order_id) {
+            Some(loc) => *loc,
+            None => return false,
+        };
+
+        let book = match side {
+            Side::Buy => &mut self.bids,
+            Side::Sell => &mut self.asks,
+        };
+
+        let order = book.get_mut(&price)
+            .and_then(|orders| orders.get_mut(order_id));
+
+        let displayed_reduction = match order {
+            Some(order) if new_quantity > 0 && new_quantity < order.leaves_quantity() => {
+                let reduction = order.leaves_quantity() - new_quantity;
+                let from_reserve = reduction.min(order.reserve_quantity);
+                order.reserve_quantity -= from_reserve;
+                order.quantity -= reduction - from_reserve;
+                if order.hidden { 0 } else { reduction - from_reserve }
+            }
+            _ => return false,
+        };
+
+        if displayed_reduction > 0 {
+            self.emit(BookEvent::OrderReduced { order_id, quantity: displayed_reduction });
+        }
+        true
+    }
+
+    /// Get a resting order by ID
+    pub fn get_order(&self, order_id: OrderId) -> Option<&Order> {
+        let (side, price) = self.order_locations.get(&order_id)?;
+        let book = match side {
+            Side::Buy => &self.bids,
+            Side::Sell => &self.asks,
+        };
+        book.get(price)?.get(order_id)
+    }
+
+    /// Number of orders ahead of a resting order at its price level
+    pub fn queue_position(&self, order_id: OrderId) -> Option<usize> {
+        let (side, price) = self.order_locations.get(&order_id)?;
+        let book = match side {
+            Side::Buy => &self.bids,
+            Side::Sell => &self.asks,
+        };
+        book.get(price)?.position(order_id)
+    }
+
+    /// Remove every resting order that has expired at time `now`
+    pub fn expire_orders(&mut self, now: u64) -> Vec<Order> {
+        self.remove_where(|o| o.is_expired(now))
+    }
+
+    /// Remove and return every resting order matching `predicate`
+    pub fn remove_where(&mut self, predicate: impl Fn(&Order) -> bool) -> Vec<Order> {
+        let mut removed = Vec::new();
+
+        for book in [&mut self.bids, &mut self.asks] {
+            for orders in book.values_mut() {
+                removed.extend(orders.remove_where(&predicate).into_iter().map(restore_reserve));
+            }
+
+            // Remove price levels left empty
+            book.retain(|_, orders| !orders.is_empty());
+        }
+
+        for order in &removed {
+            self.order_locations.remove(&order.id);
+            self.pegged.remove(&order.id);
+        }
+
+        for order in removed.iter().filter(|o| !o.hidden) {
+            self.emit(BookEvent::OrderDeleted { order_id: order.id });
+        }
+
+        removed
+    }
+
+    /// Get the highest displayed buy price
     pub fn best_bid(&self) -> Option<Price> {
-        self.bids.keys().next_back().copied()
+        self.bids.iter()
+            .rev()
+            .find(|(_, orders)| orders.has_displayed())
+            .map(|(price, _)| *price)
     }
 
-    /// Get the lowest sell price
+    /// Get the lowest displayed sell price
     pub fn best_ask(&self) -> Option<Price> {
-        self.asks.keys().next().copied()
+        self.asks.iter()
+            .find(|(_, orders)| orders.has_displayed())
+            .map(|(price, _)| *price)
+    }
+
+    /// Best displayed price on a side ignoring pegged orders, used as the
+    /// reference that pegged orders track (so pegs never follow each other)
+    pub fn reference_price(&self, side: Side) -> Option<Price> {
+        let is_reference = |(_, orders): &(&Price, &PriceLevel)| orders.displayed().any(|o| o.peg.is_none());
+        match side {
+            Side::Buy => self.bids.iter().rev().find(is_reference),
+            Side::Sell => self.asks.iter().find(is_reference),
+        }
+        .map(|(price, _)| *price)
+    }
+
+    /// IDs of all resting pegged orders, oldest order ID first
+    pub fn pegged_orders(&self) -> Vec<OrderId> {
+        if self.pegged.is_empty() {
+            return Vec::new();
+        }
+        let mut pegged: Vec<OrderId> = self.pegged.iter().copied().collect();
+        pegged.sort_unstable();
+        pegged
+    }
+
+    /// Best price on a side including hidden orders, i.e. the price an
+    /// incoming order would actually trade at first
+    pub fn best_executable_price(&self, side: Side) -> Option<Price> {
+        match side {
+            Side::Buy => self.bids.keys().next_back().copied(),
+            Side::Sell => self.asks.keys().next().copied(),
+        }
+    }
+
+    /// Quantity of `order` that could fill immediately against the opposite side
+    ///
+    /// Walks the crossing levels in priority order the way matching would, so
+    /// all-or-none orders larger than what is left are skipped. Self-trade
+    /// prevention is applied as matching would apply it: own orders are
+    /// skipped (cancel-oldest), use up the overlap without filling
+    /// (decrement-and-cancel), or end the walk (cancel-newest, cancel-both).
+    /// Resting orders matching `skip` are passed over, as in `next_match`.
+    pub fn fillable_quantity(&self, order: &Order, skip: impl Fn(&Order) -> bool) -> Quantity {
+        let book = match order.side {
+            Side::Buy => &self.asks,
+            Side::Sell => &self.bids,
+        };
+        let mut remaining = order.quantity;
+        let mut filled = 0;
+
+        'levels: for price in self.crossing_prices(order) {
+            let resting_orders = book.get(&price).into_iter().flat_map(|orders| orders.iter());
+            for resting in resting_orders {
+                if remaining == 0 {
+                    break 'levels;
+                }
+                if !resting.accepts_fill(remaining) || skip(resting) {
+                    continue;
+                }
+
+                let quantity = remaining.min(resting.leaves_quantity());
+                let self_trade = order.self_trade_prevention.filter(|_| order.is_same_owner(resting));
+                match self_trade {
+                    None => filled += quantity,
+                    Some(SelfTradePrevention::CancelOldest) => continue,
+                    Some(SelfTradePrevention::DecrementAndCancel) => {}
+                    Some(SelfTradePrevention::CancelNewest | SelfTradePrevention::CancelBoth) => break 'levels,
+                }
+                remaining -= quantity;
+            }
+        }
+
+        filled
+    }
+
+    /// Opposite-side price levels that `order` crosses, best price first
+    pub fn crossing_prices(&self, order: &Order) -> Vec<Price> {
+        match order.side {
+            Side::Buy => {
+                let limit = order.price.unwrap_or(Price::MAX);
+                self.asks.range(..=limit).map(|(price, _)| *price).collect()
+            }
+            Side::Sell => {
+                let limit = order.price.unwrap_or(0);
+                self.bids.range(limit..).rev().map(|(price, _)| *price).collect()
+            }
+        }
+    }
+
+    /// First order at an opposite-side price level that `order` can trade against
+    ///
+    /// All-or-none orders larger than `order`'s remaining quantity, and orders
+    /// matching `skip`, are passed over but keep their place in the queue.
+    pub fn next_match(&self, order: &Order, price: Price, skip: impl Fn(&Order) -> bool) -> Option<&Order> {
+        let book = match order.side {
+            Side::Buy => &self.asks,
+            Side::Sell => &self.bids,
+        };
+        book.get(&price)?
+            .iter()
+            .find(|resting| resting.accepts_fill(order.quantity) && !skip(resting))
+    }
+
+    /// Fill up to `quantity` from a resting order at a price level
+    ///
+    /// Only the displayed quantity can be filled. An iceberg whose peak is used up
+    /// refreshes from its reserve and moves to the back of the level. A fully
+    /// filled order is removed along with its location entry, and the level is
+    /// removed once empty. Returns None if the order isn't resting at `price`.
+    pub fn fill_order(&mut self, side: Side, price: Price, order_id: OrderId, quantity: Quantity) -> Option<Fill> {
+        let book = match side {
+            Side::Buy => &mut self.bids,
+            Side::Sell => &mut self.asks,
+        };
+
+        let orders = book.get_mut(&price)?;
+        let resting = orders.get_mut(order_id)?;
+        let fill = Fill {
+            order_id,
+            quantity: quantity.min(resting.quantity),
+        };
+        resting.quantity -= fill.quantity;
+        let hidden = resting.hidden;
+        let mut refreshed_peak = None;
+
+        if resting.quantity == 0 && resting.reserve_quantity > 0 {
+            // Refresh the peak from reserve and lose time priority
+            let mut refreshed = orders.remove(order_id)?;
+            let peak = refreshed.display_quantity.unwrap_or(refreshed.reserve_quantity);
+            refreshed.quantity = peak.min(refreshed.reserve_quantity);
+            refreshed.reserve_quantity -= refreshed.quantity;
+            refreshed_peak = Some(refreshed.quantity);
+            orders.push_back(refreshed);
+        } else if resting.quantity == 0 {
+            orders.remove(order_id);
+            self.order_locations.remove(&order_id);
+            self.pegged.remove(&order_id);
+
+            if orders.is_empty() {
+                book.remove(&price);
+            }
+        }
+
+        if !hidden {
+            self.emit(BookEvent::OrderExecuted { order_id, price, quantity: fill.quantity });
+        }
+        if let Some(quantity) = refreshed_peak {
+            self.emit(BookEvent::OrderAdded { order_id, side, price, quantity });
+        }
+
+        Some(fill)
+    }
+
+    /// Check that the location index and price levels are consistent
+    ///
+    /// Every resting order must have a location entry pointing at its level,
+    /// every location entry must point at a resting order, no level may be
+    /// empty, no resting order may have zero quantity, hidden orders must
+    /// sit in the hidden queue, and the pegged index must list exactly the
+    /// resting pegged orders.
+    pub fn check_invariants(&self) -> Result<(), String> {
+        let mut resting = 0;
+        let mut pegged = 0;
+
+        for (side, book) in [(Side::Buy, &self.bids), (Side::Sell, &self.asks)] {
+            for (price, orders) in book {
+                if orders.is_empty() {
+                    return Err(format!("Empty {:?} price level at {}", side, price));
+                }
+
+                if let Some(order) = orders.displayed().find(|o| o.hidden) {
+                    return Err(format!("Hidden order #{} is in the displayed queue", order.id));
+                }
+                if let Some(order) = orders.hidden().find(|o| !o.hidden) {
+                    return Err(format!("Displayed order #{} is in the hidden queue", order.id));
+                }
+
+                for order in orders.iter() {
+                    if order.side != side || order.price != Some(*price) {
+                        return Err(format!("Order #{} is queued at the wrong level ({:?} {})", order.id, side, price));
+                    }
+                    if order.quantity == 0 {
+                        return Err(format!("Order #{} is resting with zero quantity", order.id));
+                    }
+                    if order.reserve_quantity > 0 && order.display_quantity.is_none() {
+                        return Err(format!("Order #{} has a reserve but no display quantity", order.id));
+                    }
+                    if self.order_locations.get(&order.id) != Some(&(side, *price)) {
+                        return Err(format!("Order #{} has a missing or wrong location entry", order.id));
+                    }
+                    if order.peg.is_some() {
+                        if !self.pegged.contains(&order.id) {
+                            return Err(format!("Pegged order #{} is missing from the pegged index", order.id));
+                        }
+                        pegged += 1;
+                    }
+                    resting += 1;
+                }
+            }
+        }
+
+        if resting != self.order_locations.len() {
+            return Err(format!(
+                "Location index has {} entries for {} resting orders",
+                self.order_locations.len(),
+                resting
+            ));
+        }
+
+        if pegged != self.pegged.len() {
+            return Err(format!(
+                "Pegged index has {} entries for {} resting pegged orders",
+                self.pegged.len(),
+                pegged
+            ));
+        }
+
+        Ok(())
     }
 
-    /// Get mutable reference to bids
-    pub fn bids_mut(&mut self) -> &mut BTreeMap<Price, VecDeque<Order>> {
-        &mut self.bids
+    /// Top `levels` displayed price levels per side, with total quantity and order count
+    ///
+    /// Hidden orders and iceberg reserves are not included.
+    pub fn depth(&self, levels: usize) -> Depth {
+        let aggregate = |(price, orders): (&Price, &PriceLevel)| DepthLevel {
+            price: *price,
+            quantity: orders.displayed_quantity(),
+            order_count: orders.displayed().count(),
+        };
+
+        Depth {
+            bids: self.bids.iter()
+                .rev()
+                .filter(|(_, orders)| orders.has_displayed())
+                .take(levels)
+                .map(aggregate)
+                .collect(),
+            asks: self.asks.iter()
+                .filter(|(_, orders)| orders.has_displayed())
+                .take(levels)
+                .map(aggregate)
+                .collect(),
+        }
     }
 
-    /// Get mutable reference to asks
-    pub fn asks_mut(&mut self) -> &mut BTreeMap<Price, VecDeque<Order>> {
-        &mut self.asks
+    /// Every resting order on one side in priority order: best price first,
+    /// then displayed before hidden, then time. Hidden orders are included.
+    pub fn orders(&self, side: Side) -> impl Iterator<Item = BookOrder> + '_ {
+        let levels: Box<dyn Iterator<Item = (&Price, &PriceLevel)>> = match side {
+            Side::Buy => Box::new(self.bids.iter().rev()),
+            Side::Sell => Box::new(self.asks.iter()),
+        };
+
+        levels.flat_map(move |(price, orders)| {
+            orders.iter().enumerate().map(move |(queue_position, order)| BookOrder {
+                order_id: order.id,
+                side,
+                price: *price,
+                quantity: order.leaves_quantity(),
+                displayed_quantity: if order.hidden { 0 } else { order.quantity },
+                timestamp: order.timestamp,
+                queue_position,
+            })
+        })
     }
 
     pub fn display(&self) {
+        let depth = self.depth(usize::MAX);
+
         println!("\nORDER BOOK");
         println!("==========");
         
         // Display asks (lowest first, so reverse)
         println!("ASKS (Sells):");
-        let mut ask_levels: Vec<_> = self.asks.iter().collect();
-        ask_levels.reverse();
-        
-        for (price, orders) in ask_levels {
-            let total_qty: u64 = orders.iter().map(|o| o.quantity).sum();
-            let num_orders = orders.len();
-            println!(
-                "  ${:>7.2}  |  {:>4} shares  ({} order{})",
-                *price as f64 / 100.0,
-                total_qty,
-                num_orders,
-                if num_orders == 1 { "" } else { "s" }
-            );
+        for level in depth.asks.iter().rev() {
+            print_level(level);
         }
         
         // Separator
@@ -121,18 +540,27 @@ impl OrderBook {
         
         // Display bids (highest first)
         println!("BIDS (Buys):");
-        for (price, orders) in self.bids.iter().rev() {
-            let total_qty: u64 = orders.iter().map(|o| o.quantity).sum();
-            let num_orders = orders.len();
-            let oldest_timestamp = orders.front().map(|o| o.timestamp);
-            println!(
-                "  ${:>7.2}  |  {:>4} shares  ({} order{})",
-                *price as f64 / 100.0,
-                total_qty,
-                num_orders,
-                if num_orders == 1 { "" } else { "s" },
-            );
+        for level in &depth.bids {
+            print_level(level);
         }
         println!();  // Extra newline at end
     }
-}
\ No newline at end of file
+}
+
+/// Print one aggregated price level for `display()`
+fn print_level(level: &DepthLevel) {
+    println!(
+        "  ${:>7.2}  |  {:>4} shares  ({} order{})",
+        level.price as f64 / 100.0,
+        level.quantity,
+        level.order_count,
+        if level.order_count == 1 { "" } else { "s" }
+    );
+}
+
+/// Fold an iceberg's hidden reserve back into its quantity once it leaves the book
+fn restore_reserve(mut order: Order) -> Order {
+    order.quantity += order.reserve_quantity;
+    order.reserve_quantity = 0;
+    order
+}
@@ -0,0 +1,35 @@
+use crate::types::{OrderId, Price, Quantity, Side};
+
+/// Aggregated displayed liquidity at one price
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DepthLevel {
+    pub price: Price,
+    /// Total displayed quantity (iceberg peaks only, no hidden orders)
+    pub quantity: Quantity,
+    /// Number of displayed orders
+    pub order_count: usize,
+}
+
+/// Level-2 snapshot of the top of a book, best prices first on each side
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Depth {
+    /// Bid levels, highest price first
+    pub bids: Vec<DepthLevel>,
+    /// Ask levels, lowest price first
+    pub asks: Vec<DepthLevel>,
+}
+
+/// Level-3 view of one resting order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BookOrder {
+    pub order_id: OrderId,
+    pub side: Side,
+    pub price: Price,
+    /// Open quantity, including any iceberg reserve
+    pub quantity: Quantity,
+    /// Quantity shown in the book (zero for hidden orders)
+    pub displayed_quantity: Quantity,
+    pub timestamp: u64,
+    /// Number of orders ahead of it at its price level
+    pub queue_position: usize,
+}
@@ -0,0 +1,29 @@
+use crate::types::{OrderId, Price, Quantity, Side, TradeId};
+
+/// Change to a book's displayed liquidity, or a trade print
+///
+/// Hidden orders never appear except through `TradePrinted`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BookEvent {
+    /// Order joined the back of the queue at `price` showing `quantity`
+    /// (also sent when an iceberg refreshes its peak)
+    OrderAdded { order_id: OrderId, side: Side, price: Price, quantity: Quantity },
+    /// Displayed quantity cut by `quantity` without trading (in-place amend,
+    /// self-trade prevention)
+    OrderReduced { order_id: OrderId, quantity: Quantity },
+    /// Order removed without trading (cancel, expiry, reprice)
+    OrderDeleted { order_id: OrderId },
+    /// Resting order traded `quantity` at `price`; it leaves the book once
+    /// its displayed quantity reaches zero
+    OrderExecuted { order_id: OrderId, price: Price, quantity: Quantity },
+    /// Trade print for every execution, including against hidden orders
+    TradePrinted { trade_id: TradeId, price: Price, quantity: Quantity, aggressor_side: Side },
+}
+
+/// Book event with its per-symbol sequence number
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SequencedEvent {
+    /// Monotonically increasing per book, starting at 1
+    pub sequence: u64,
+    pub event: BookEvent,
+}
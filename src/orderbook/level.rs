@@ -0,0 +1,107 @@
+use std::collections::VecDeque;
+use crate::types::{Order, OrderId, Quantity};
+
+/// All resting orders at one price, in priority order
+///
+/// Displayed orders always have priority over hidden orders at the same price;
+/// each queue is FIFO on its own.
+#[derive(Debug, Clone, Default)]
+pub struct PriceLevel {
+    /// Orders visible in depth and display output
+    displayed: VecDeque<Order>,
+
+    /// Hidden (non-displayed) orders, matched only after the displayed queue
+    hidden: VecDeque<Order>,
+}
+
+impl PriceLevel {
+    /// Add an order to the back of its queue
+    pub fn push_back(&mut self, order: Order) {
+        if order.hidden {
+            self.hidden.push_back(order);
+        } else {
+            self.displayed.push_back(order);
+        }
+    }
+
+    /// Remove an order by ID, keeping the others in priority order
+    pub fn remove(&mut self, order_id: OrderId) -> Option<Order> {
+        for queue in [&mut self.displayed, &mut self.hidden] {
+            if let Some(pos) = queue.iter().position(|o| o.id == order_id) {
+                return queue.remove(pos);
+            }
+        }
+        None
+    }
+
+    /// Get an order by ID
+    pub fn get(&self, order_id: OrderId) -> Option<&Order> {
+        self.iter().find(|o| o.id == order_id)
+    }
+
+    /// Get a mutable order by ID
+    pub fn get_mut(&mut self, order_id: OrderId) -> Option<&mut Order> {
+        self.displayed
+            .iter_mut()
+            .chain(self.hidden.iter_mut())
+            .find(|o| o.id == order_id)
+    }
+
+    /// Number of orders ahead of an order in priority order
+    pub fn position(&self, order_id: OrderId) -> Option<usize> {
+        self.iter().position(|o| o.id == order_id)
+    }
+
+    /// Remove and return every order matching `predicate`
+    pub fn remove_where(&mut self, predicate: impl Fn(&Order) -> bool) -> Vec<Order> {
+        let mut removed = Vec::new();
+
+        for queue in [&mut self.displayed, &mut self.hidden] {
+            let mut i = 0;
+            while i < queue.len() {
+                if predicate(&queue[i]) {
+                    // remove() keeps the remaining orders in time priority
+                    removed.extend(queue.remove(i));
+                } else {
+                    i += 1;
+                }
+            }
+        }
+
+        removed
+    }
+
+    /// All orders in priority order (displayed first, then hidden)
+    pub fn iter(&self) -> impl Iterator<Item = &Order> {
+        self.displayed.iter().chain(self.hidden.iter())
+    }
+
+    /// Displayed orders only, in priority order
+    pub fn displayed(&self) -> impl Iterator<Item = &Order> {
+        self.displayed.iter()
+    }
+
+    /// Hidden orders only, in priority order
+    pub fn hidden(&self) -> impl Iterator<Item = &Order> {
+        self.hidden.iter()
+    }
+
+    /// Quantity visible in the book (iceberg peaks only, no hidden orders)
+    pub fn displayed_quantity(&self) -> Quantity {
+        self.displayed.iter().map(|o| o.quantity).sum()
+    }
+
+    /// Check if any order at this price is displayed
+    pub fn has_displayed(&self) -> bool {
+        !self.displayed.is_empty()
+    }
+
+    /// Number of orders (displayed and hidden)
+    pub fn len(&self) -> usize {
+        self.displayed.len() + self.hidden.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.displayed.is_empty() && self.hidden.is_empty()
+    }
+}
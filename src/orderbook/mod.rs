@@ -1,3 +1,11 @@
 mod book;
+mod depth;
+mod events;
+mod level;
+mod triggers;
 
-pub use book::OrderBook;
\ No newline at end of file
+pub use book::{Fill, OrderBook};
+pub use depth::{BookOrder, Depth, DepthLevel};
+pub use events::{BookEvent, SequencedEvent};
+pub use level::PriceLevel;
+pub use triggers::TriggerBook;
\ No newline at end of file
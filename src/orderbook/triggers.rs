@@ -0,0 +1,177 @@
+use std::collections::{BTreeMap, VecDeque, HashMap};
+use crate::types::{Order, OrderId, Price, Side};
+
+/// Stop orders waiting for their trigger price, kept off the visible book
+pub struct TriggerBook {
+    /// Buy stops, fire when the last trade price rises to or above the trigger
+    buy_stops: BTreeMap<Price, VecDeque<Order>>,
+
+    /// Sell stops, fire when the last trade price falls to or below the trigger
+    sell_stops: BTreeMap<Price, VecDeque<Order>>,
+
+    /// Store order locations for O(1) lookup by Order ID
+    order_locations: HashMap<OrderId, (Side, Price)>,
+}
+
+impl Default for TriggerBook {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TriggerBook {
+    pub fn new() -> Self {
+        TriggerBook {
+            buy_stops: BTreeMap::new(),
+            sell_stops: BTreeMap::new(),
+            order_locations: HashMap::new(),
+        }
+    }
+
+    /// Park a stop order until its trigger price trades
+    pub fn add_order(&mut self, order: Order) {
+        let trigger = match order.trigger_price {
+            Some(p) => p,
+            None => return,
+        };
+
+        let side = order.side;
+        let order_id = order.id;
+
+        let stops = match side {
+            Side::Buy => &mut self.buy_stops,
+            Side::Sell => &mut self.sell_stops,
+        };
+
+        stops.entry(trigger)
+            .or_default()
+            .push_back(order);
+
+        self.order_locations.insert(order_id, (side, trigger));
+    }
+
+    /// Remove a pending stop order, returning it if it was waiting
+    pub fn remove_order(&mut self, order_id: OrderId) -> Option<Order> {
+        let (side, trigger) = self.order_locations.remove(&order_id)?;
+
+        let stops = match side {
+            Side::Buy => &mut self.buy_stops,
+            Side::Sell => &mut self.sell_stops,
+        };
+
+        let orders = stops.get_mut(&trigger)?;
+        let pos = orders.iter().position(|o| o.id == order_id)?;
+        let order = orders.remove(pos);
+
+        if orders.is_empty() {
+            stops.remove(&trigger);
+        }
+
+        order
+    }
+
+    /// Get a pending stop order by ID
+    pub fn get_order(&self, order_id: OrderId) -> Option<&Order> {
+        let (side, trigger) = self.order_locations.get(&order_id)?;
+        let stops = match side {
+            Side::Buy => &self.buy_stops,
+            Side::Sell => &self.sell_stops,
+        };
+        stops.get(trigger)?.iter().find(|o| o.id == order_id)
+    }
+
+    /// Move trailing stop triggers to follow a new last trade price
+    ///
+    /// Call once per print, in order, so a sweep's highs and lows are not lost.
+    /// Triggers only ever move in the order's favour; a trailing stop whose
+    /// trigger moves goes to the back of its new trigger level.
+    pub fn ratchet_trailing_stops(&mut self, last_price: Price) {
+        let mut moves = Vec::new();
+
+        for (side, stops) in [(Side::Buy, &self.buy_stops), (Side::Sell, &self.sell_stops)] {
+            for (trigger, orders) in stops {
+                for order in orders {
+                    let new_trigger = match order.trailing_trigger(last_price) {
+                        Some(price) => price,
+                        None => continue,  // Not a trailing stop
+                    };
+                    let tighter = match side {
+                        Side::Buy => new_trigger < *trigger,
+                        Side::Sell => new_trigger > *trigger,
+                    };
+                    if tighter {
+                        moves.push((order.id, new_trigger));
+                    }
+                }
+            }
+        }
+
+        for (order_id, new_trigger) in moves {
+            if let Some(mut order) = self.remove_order(order_id) {
+                order.trigger_price = Some(new_trigger);
+                self.add_order(order);
+            }
+        }
+    }
+
+    /// Remove and return every stop triggered by a trade at `last_price`
+    ///
+    /// Stops fire in the order the price moved through them, FIFO within a trigger price.
+    pub fn take_triggered(&mut self, last_price: Price) -> Vec<Order> {
+        let mut triggered = Vec::new();
+
+        // Buy stops at or below the last price, lowest trigger first
+        let buy_triggers: Vec<Price> = self.buy_stops.range(..=last_price).map(|(p, _)| *p).collect();
+        for trigger in buy_triggers {
+            triggered.extend(self.buy_stops.remove(&trigger).unwrap_or_default());
+        }
+
+        // Sell stops at or above the last price, highest trigger first
+        let sell_triggers: Vec<Price> = self.sell_stops.range(last_price..).map(|(p, _)| *p).rev().collect();
+        for trigger in sell_triggers {
+            triggered.extend(self.sell_stops.remove(&trigger).unwrap_or_default());
+        }
+
+        for order in &triggered {
+            self.order_locations.remove(&order.id);
+        }
+
+        triggered
+    }
+
+    /// Remove every pending stop that has expired at time `now`
+    pub fn expire_orders(&mut self, now: u64) -> Vec<Order> {
+        let mut expired = Vec::new();
+
+        for stops in [&mut self.buy_stops, &mut self.sell_stops] {
+            for orders in stops.values_mut() {
+                let mut i = 0;
+                while i < orders.len() {
+                    if orders[i].is_expired(now) {
+                        expired.extend(orders.remove(i));
+                    } else {
+                        i += 1;
+                    }
+                }
+            }
+
+            stops.retain(|_, orders| !orders.is_empty());
+        }
+
+        for order in &expired {
+            self.order_locations.remove(&order.id);
+        }
+
+        expired
+    }
+
+    /// Number of pending stop orders
+    pub fn len(&self) -> usize {
+        self.order_locations.len()
+    }
+
+    /// Check if there are no pending stop orders
+    pub fn is_empty(&self) -> bool {
+        self.order_locations.is_empty()
+    }
+}
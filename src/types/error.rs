@@ -0,0 +1,70 @@
+use super::{ClientOrderId, OrderId};
+use std::error::Error;
+use std::fmt;
+
+/// Reason an order or order request was rejected by the engine
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum OrderError {
+    /// Quantity was zero
+    ZeroQuantity,
+    /// Symbol was an empty string
+    EmptySymbol,
+    /// Limit order submitted without a price
+    MissingLimitPrice,
+    /// Limit price was zero
+    ZeroPrice,
+    /// Stop or stop-limit order submitted without a (non-zero) trigger price
+    MissingTriggerPrice,
+    /// Trailing stop submitted without a valid offset
+    InvalidTrailingOffset,
+    /// No last trade price to anchor a trailing stop to
+    NoReferencePrice,
+    /// Good-Till-Date expiry is not in the future
+    ExpiryInPast,
+    /// Iceberg display quantity was zero, or set on a market, hidden or all-or-none order
+    InvalidDisplayQuantity,
+    /// Minimum quantity was zero or larger than the order quantity
+    InvalidMinQuantity,
+    /// Post-only flag on an order that isn't a resting limit order
+    InvalidPostOnly,
+    /// Post-only order would have taken liquidity
+    WouldTakeLiquidity,
+    /// Client order ID is already used by a live order from the same account
+    DuplicateClientOrderId,
+    /// Order group legs must be resting orders in the same symbol that can't
+    /// trade with each other
+    InvalidOrderGroup,
+    /// Market order rejected because the opposite side of the book is empty
+    NoLiquidity,
+    /// No live order with this ID exists in the given symbol
+    OrderNotFound(OrderId),
+    /// No live order with this client order ID exists for the account
+    ClientOrderIdNotFound(ClientOrderId),
+}
+
+impl fmt::Display for OrderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OrderError::ZeroQuantity => write!(f, "Invalid order: Quantity must be greater than 0"),
+            OrderError::EmptySymbol => write!(f, "Invalid order: Symbol cannot be empty"),
+            OrderError::MissingLimitPrice => write!(f, "Invalid order: Limit order requires price"),
+            OrderError::ZeroPrice => write!(f, "Invalid order: Price must be greater than 0"),
+            OrderError::MissingTriggerPrice => write!(f, "Invalid order: Stop order requires a trigger price greater than 0"),
+            OrderError::InvalidTrailingOffset => write!(f, "Invalid order: Trailing stop requires an offset greater than 0 (and below 100%)"),
+            OrderError::NoReferencePrice => write!(f, "Order rejected: No reference price available"),
+            OrderError::ExpiryInPast => write!(f, "Invalid order: Expiry time must be in the future"),
+            OrderError::InvalidDisplayQuantity => write!(f, "Invalid order: Display quantity must be greater than 0 on a displayed limit order"),
+            OrderError::InvalidMinQuantity => write!(f, "Invalid order: Minimum quantity must be between 1 and the order quantity"),
+            OrderError::InvalidPostOnly => write!(f, "Invalid order: Post-only requires a limit order that can rest"),
+            OrderError::WouldTakeLiquidity => write!(f, "Order rejected: Post-only order would take liquidity"),
+            OrderError::DuplicateClientOrderId => write!(f, "Order rejected: Duplicate client order ID"),
+            OrderError::InvalidOrderGroup => write!(f, "Invalid order: Order group legs must be non-crossing resting orders in the same symbol"),
+            OrderError::NoLiquidity => write!(f, "Order rejected: No liquidity on the opposite side"),
+            OrderError::OrderNotFound(id) => write!(f, "Invalid request: Order #{} not found", id),
+            OrderError::ClientOrderIdNotFound(id) => write!(f, "Invalid request: Client order ID {} not found", id),
+        }
+    }
+}
+
+impl Error for OrderError {}
@@ -1,5 +1,11 @@
+mod error;
 mod order;
+mod report;
+mod status;
 mod trade;
 
-pub use order::{Order, OrderId, Symbol, OrderType, Price, Quantity, Side};
-pub use trade::Trade;
\ No newline at end of file
+pub use order::{AccountId, ClientOrderId, Order, OrderId, Symbol, OrderType, Peg, PegType, PostOnly, Price, Quantity, SelfTradePrevention, Side, TimeInForce, TrailingOffset};
+pub use error::OrderError;
+pub use report::ExecutionReport;
+pub use status::{OrderState, OrderStatus};
+pub use trade::{Trade, TradeId};
\ No newline at end of file
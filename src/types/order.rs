@@ -12,6 +12,15 @@ pub type Price = u64;
 /// Quantity of the asset
 pub type Quantity = u64;
 
+/// Account/trader that owns an order
+pub type AccountId = String;
+
+/// Client-assigned order ID (FIX ClOrdID style), unique per account among live orders
+pub type ClientOrderId = String;
+
+/// Nanoseconds in one day, used to find the end of a DAY order's session
+const NANOS_PER_DAY: u64 = 24 * 60 * 60 * 1_000_000_000;
+
 /// Is the Order Buy or Sell
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Side {
@@ -19,13 +28,146 @@ pub enum Side {
     Sell,
 }
 
-/// Type of order (Market, Limit)
+impl Side {
+    /// The other side of the book
+    pub fn opposite(&self) -> Side {
+        match self {
+            Side::Buy => Side::Sell,
+            Side::Sell => Side::Buy,
+        }
+    }
+}
+
+/// Type of order (Market, Limit, Stop, StopLimit, TrailingStop, Pegged)
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum OrderType {
     /// Market order - executes immediately at best available price
     Market,
     /// Limit order - executes only at specified price or better
     Limit,
+    /// Stop order - becomes a market order once the trigger price trades
+    Stop,
+    /// Stop-limit order - becomes a limit order once the trigger price trades
+    StopLimit,
+    /// Trailing stop - stop order whose trigger follows the last trade price
+    TrailingStop,
+    /// Pegged order - limit order whose price follows the best bid/offer
+    Pegged,
+}
+
+/// Distance a trailing stop keeps from the last trade price
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrailingOffset {
+    /// Fixed amount in the smallest currency unit
+    Absolute(Price),
+    /// Fraction of the last trade price in basis points (100 = 1%)
+    BasisPoints(u64),
+}
+
+impl TrailingOffset {
+    /// Offset amount at the given reference price
+    pub fn amount(&self, reference_price: Price) -> Price {
+        match *self {
+            TrailingOffset::Absolute(amount) => amount,
+            TrailingOffset::BasisPoints(bps) => (reference_price * bps / 10_000).max(1),
+        }
+    }
+
+    /// Check the offset is non-zero (and below 100% for percentages)
+    pub fn is_valid(&self) -> bool {
+        match *self {
+            TrailingOffset::Absolute(amount) => amount > 0,
+            TrailingOffset::BasisPoints(bps) => bps > 0 && bps < 10_000,
+        }
+    }
+}
+
+/// Book price a pegged order tracks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PegType {
+    /// Midpoint of the best bid and best ask (rounded down for buys, up for sells)
+    Midpoint,
+    /// Best price on the order's own side
+    Primary,
+    /// Best price on the opposite side
+    Market,
+}
+
+/// Pegging instructions for a pegged order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Peg {
+    pub peg_type: PegType,
+    /// Signed amount added to the reference price
+    pub offset: i64,
+    /// Worst price the order may be pegged to (a cap for buys, a floor for sells)
+    pub limit: Option<Price>,
+}
+
+impl Peg {
+    /// Pegged price for the given side and reference best bid/ask
+    ///
+    /// Returns None if the reference price doesn't exist (e.g. a midpoint peg
+    /// with one side of the book empty) or the offset takes it to zero or below.
+    pub fn price(&self, side: Side, best_bid: Option<Price>, best_ask: Option<Price>) -> Option<Price> {
+        let reference = match (self.peg_type, side) {
+            (PegType::Midpoint, Side::Buy) => (best_bid? + best_ask?) / 2,
+            (PegType::Midpoint, Side::Sell) => (best_bid? + best_ask?).div_ceil(2),
+            (PegType::Primary, Side::Buy) | (PegType::Market, Side::Sell) => best_bid?,
+            (PegType::Primary, Side::Sell) | (PegType::Market, Side::Buy) => best_ask?,
+        };
+
+        let pegged = reference.checked_add_signed(self.offset).filter(|&price| price > 0)?;
+        Some(match (side, self.limit) {
+            (Side::Buy, Some(limit)) => pegged.min(limit),
+            (Side::Sell, Some(limit)) => pegged.max(limit),
+            (_, None) => pegged,
+        })
+    }
+}
+
+/// How long an order stays active before it is cancelled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeInForce {
+    /// Good-Till-Cancel - any unfilled remainder rests on the book
+    GoodTillCancel,
+    /// Immediate-or-Cancel - fill what is possible, cancel the rest
+    ImmediateOrCancel,
+    /// Fill-or-Kill - fill the entire quantity immediately or not at all
+    FillOrKill,
+    /// Day - rests until the end of the (UTC) day it was accepted on
+    Day,
+    /// Good-Till-Date - rests until the given timestamp (nanoseconds since epoch)
+    GoodTillDate(u64),
+}
+
+impl TimeInForce {
+    /// Check if an unfilled remainder should be cancelled instead of resting
+    pub fn is_immediate(&self) -> bool {
+        matches!(self, TimeInForce::ImmediateOrCancel | TimeInForce::FillOrKill)
+    }
+}
+
+/// What to do when an incoming order would trade against a resting order
+/// from the same account
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelfTradePrevention {
+    /// Cancel the incoming order's remaining quantity
+    CancelNewest,
+    /// Cancel the resting order and keep matching
+    CancelOldest,
+    /// Cancel both the resting order and the incoming remainder
+    CancelBoth,
+    /// Reduce both orders by the smaller quantity, cancelling whichever reaches zero
+    DecrementAndCancel,
+}
+
+/// What to do with a post-only order that would take liquidity on arrival
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PostOnly {
+    /// Reject the order
+    Reject,
+    /// Reprice the order one tick passive of the opposite best price
+    Slide,
 }
 
 /// Represents a single order in the system
@@ -36,8 +178,21 @@ pub struct Order {
     pub side: Side,
     pub order_type: OrderType,
     pub price: Option<Price>,   // We use Option because market orders won't take a price. AKA nullable
+    pub trigger_price: Option<Price>,  // Stop trigger; None for non-stop orders
+    pub trailing_offset: Option<TrailingOffset>,  // Trail distance for trailing stops
+    pub peg: Option<Peg>,  // Peg instructions for pegged orders; price is set by the engine
     pub quantity: Quantity,
     pub timestamp: u64,
+    pub time_in_force: TimeInForce,
+    pub owner: Option<AccountId>,
+    pub client_order_id: Option<ClientOrderId>,
+    pub self_trade_prevention: Option<SelfTradePrevention>,
+    pub post_only: Option<PostOnly>,         // Maker-only; never takes liquidity
+    pub hidden: bool,                        // Never displayed; lower priority than displayed orders
+    pub min_quantity: Option<Quantity>,      // Smallest acceptable immediate fill on arrival
+    pub all_or_none: bool,                   // Only ever fill the whole remaining quantity at once
+    pub display_quantity: Option<Quantity>,  // Iceberg peak size; None shows the full quantity
+    pub reserve_quantity: Quantity,          // Hidden iceberg reserve while resting (managed by the book)
 }
 
 // Define methods for Order struct
@@ -55,8 +210,21 @@ impl Order {
             side,
             order_type: OrderType::Limit,
             price: Some(price),
+            trigger_price: None,
+            trailing_offset: None,
+            peg: None,
             quantity,
             timestamp: 0,  // Will be set by engine
+            time_in_force: TimeInForce::GoodTillCancel,
+            owner: None,
+            client_order_id: None,
+            self_trade_prevention: None,
+            post_only: None,
+            hidden: false,
+            min_quantity: None,
+            all_or_none: false,
+            display_quantity: None,
+            reserve_quantity: 0,
         }
     }
 
@@ -72,11 +240,214 @@ impl Order {
             side,
             order_type: OrderType::Market,
             price: None,
+            trigger_price: None,
+            trailing_offset: None,
+            peg: None,
             quantity,
             timestamp: 0,  // Will be set by engine
+            time_in_force: TimeInForce::GoodTillCancel,
+            owner: None,
+            client_order_id: None,
+            self_trade_prevention: None,
+            post_only: None,
+            hidden: false,
+            min_quantity: None,
+            all_or_none: false,
+            display_quantity: None,
+            reserve_quantity: 0,
+        }
+    }
+
+    /// Create a stop order that becomes a market order when `trigger_price` trades
+    pub fn stop(
+        symbol: Symbol,
+        side: Side,
+        trigger_price: Price,
+        quantity: Quantity,
+    ) -> Self {
+        Order {
+            order_type: OrderType::Stop,
+            trigger_price: Some(trigger_price),
+            ..Order::market(symbol, side, quantity)
         }
     }
 
+    /// Create a stop-limit order that becomes a limit order at `price` when `trigger_price` trades
+    pub fn stop_limit(
+        symbol: Symbol,
+        side: Side,
+        trigger_price: Price,
+        price: Price,
+        quantity: Quantity,
+    ) -> Self {
+        Order {
+            order_type: OrderType::StopLimit,
+            trigger_price: Some(trigger_price),
+            ..Order::limit(symbol, side, price, quantity)
+        }
+    }
+
+    /// Create a trailing stop that becomes a market order once price reverses by `offset`
+    ///
+    /// The trigger starts `offset` away from the last trade price and only moves
+    /// in the order's favour: up for sell stops, down for buy stops.
+    pub fn trailing_stop(
+        symbol: Symbol,
+        side: Side,
+        offset: TrailingOffset,
+        quantity: Quantity,
+    ) -> Self {
+        Order {
+            order_type: OrderType::TrailingStop,
+            trailing_offset: Some(offset),
+            ..Order::market(symbol, side, quantity)
+        }
+    }
+
+    /// Create a pegged order whose price follows the book's best bid/offer
+    ///
+    /// The engine sets (and keeps updating) the price from `peg_type`'s reference.
+    pub fn pegged(
+        symbol: Symbol,
+        side: Side,
+        peg_type: PegType,
+        quantity: Quantity,
+    ) -> Self {
+        Order {
+            order_type: OrderType::Pegged,
+            peg: Some(Peg { peg_type, offset: 0, limit: None }),
+            ..Order::market(symbol, side, quantity)
+        }
+    }
+
+    /// Set a pegged order's offset from its reference price
+    pub fn with_peg_offset(mut self, offset: i64) -> Self {
+        if let Some(peg) = self.peg.as_mut() {
+            peg.offset = offset;
+        }
+        self
+    }
+
+    /// Cap a pegged order's price (maximum for buys, minimum for sells)
+    pub fn with_peg_limit(mut self, limit: Price) -> Self {
+        if let Some(peg) = self.peg.as_mut() {
+            peg.limit = Some(limit);
+        }
+        self
+    }
+
+    /// Set the time-in-force (defaults to Good-Till-Cancel)
+    pub fn with_time_in_force(mut self, time_in_force: TimeInForce) -> Self {
+        self.time_in_force = time_in_force;
+        self
+    }
+
+    /// Set the owning account
+    pub fn with_owner(mut self, owner: AccountId) -> Self {
+        self.owner = Some(owner);
+        self
+    }
+
+    /// Set the client-assigned order ID
+    pub fn with_client_order_id(mut self, client_order_id: ClientOrderId) -> Self {
+        self.client_order_id = Some(client_order_id);
+        self
+    }
+
+    /// Set the self-trade prevention mode applied when this order is the aggressor
+    pub fn with_self_trade_prevention(mut self, mode: SelfTradePrevention) -> Self {
+        self.self_trade_prevention = Some(mode);
+        self
+    }
+
+    /// Make this a post-only (maker-only) order
+    pub fn with_post_only(mut self, mode: PostOnly) -> Self {
+        self.post_only = Some(mode);
+        self
+    }
+
+    /// Make this a hidden (non-displayed) order
+    pub fn with_hidden(mut self, hidden: bool) -> Self {
+        self.hidden = hidden;
+        self
+    }
+
+    /// Require at least `min_quantity` to be fillable on arrival before the order trades
+    pub fn with_min_quantity(mut self, min_quantity: Quantity) -> Self {
+        self.min_quantity = Some(min_quantity);
+        self
+    }
+
+    /// Make this an all-or-none order that only fills its whole remaining quantity
+    pub fn with_all_or_none(mut self, all_or_none: bool) -> Self {
+        self.all_or_none = all_or_none;
+        self
+    }
+
+    /// Make this an iceberg order that only shows `display_quantity` at a time
+    pub fn with_display_quantity(mut self, display_quantity: Quantity) -> Self {
+        self.display_quantity = Some(display_quantity);
+        self
+    }
+
+    /// Total open quantity, including any hidden iceberg reserve
+    pub fn leaves_quantity(&self) -> Quantity {
+        self.quantity + self.reserve_quantity
+    }
+
+    /// Quantity that must be fillable on arrival before the order may trade at all
+    pub fn required_fill_quantity(&self) -> Quantity {
+        if self.all_or_none || self.time_in_force == TimeInForce::FillOrKill {
+            self.quantity
+        } else {
+            self.min_quantity.unwrap_or(0).min(self.quantity)
+        }
+    }
+
+    /// Check if a resting order can take a fill from an incoming order with
+    /// `quantity` left (all-or-none orders need enough to fill completely)
+    pub fn accepts_fill(&self, quantity: Quantity) -> bool {
+        !self.all_or_none || quantity >= self.leaves_quantity()
+    }
+
+    /// Check if both orders belong to the same (known) account
+    pub fn is_same_owner(&self, other: &Order) -> bool {
+        self.owner.is_some() && self.owner == other.owner
+    }
+
+    /// Timestamp at which a resting order expires, if it ever does
+    pub fn expires_at(&self) -> Option<u64> {
+        match self.time_in_force {
+            TimeInForce::Day => Some((self.timestamp / NANOS_PER_DAY + 1) * NANOS_PER_DAY),
+            TimeInForce::GoodTillDate(expiry) => Some(expiry),
+            _ => None,
+        }
+    }
+
+    /// Check if the order has expired at time `now`
+    pub fn is_expired(&self, now: u64) -> bool {
+        self.expires_at().is_some_and(|expiry| now >= expiry)
+    }
+
+    /// Check if this is a stop order still waiting for its trigger
+    pub fn is_stop(&self) -> bool {
+        matches!(self.order_type, OrderType::Stop | OrderType::StopLimit | OrderType::TrailingStop)
+    }
+
+    /// Trigger price a trailing stop would have with the last trade at `last_price`
+    pub fn trailing_trigger(&self, last_price: Price) -> Option<Price> {
+        let offset = self.trailing_offset?.amount(last_price);
+        Some(match self.side {
+            Side::Buy => last_price.saturating_add(offset),
+            Side::Sell => last_price.saturating_sub(offset),
+        })
+    }
+
+    /// Check if this order needs a limit price (Limit, StopLimit)
+    pub fn requires_price(&self) -> bool {
+        matches!(self.order_type, OrderType::Limit | OrderType::StopLimit)
+    }
+
     /// Check if this is a buy order
     pub fn is_buy(&self) -> bool {
         self.side == Side::Buy
@@ -110,6 +481,41 @@ impl fmt::Display for Order {
                     self.quantity
                 )
             }
+            OrderType::Stop => {
+                write!(
+                    f,
+                    "Order #{}: {:?} STOP trigger {} {} qty",
+                    self.id,
+                    self.side,
+                    self.trigger_price.unwrap(),    // Stop orders must take a trigger price
+                    self.quantity
+                )
+            }
+            OrderType::TrailingStop => {
+                write!(
+                    f,
+                    "Order #{}: {:?} TRAILING STOP {:?} trigger {:?} {} qty",
+                    self.id, self.side, self.trailing_offset, self.trigger_price, self.quantity
+                )
+            }
+            OrderType::Pegged => {
+                write!(
+                    f,
+                    "Order #{}: {:?} PEGGED {:?} @ {:?} {} qty",
+                    self.id, self.side, self.peg, self.price, self.quantity
+                )
+            }
+            OrderType::StopLimit => {
+                write!(
+                    f,
+                    "Order #{}: {:?} STOP LIMIT trigger {} limit {} @ {} qty",
+                    self.id,
+                    self.side,
+                    self.trigger_price.unwrap(),
+                    self.price.unwrap(),
+                    self.quantity
+                )
+            }
         }
     }
 }
@@ -0,0 +1,61 @@
+use super::{OrderId, Quantity, Trade};
+
+/// Outcome of submitting (or amending) an order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecutionReport {
+    /// ID assigned to the order by the engine
+    pub order_id: OrderId,
+    /// Time the order was accepted (nanoseconds since epoch)
+    pub timestamp: u64,
+    /// Quantity filled by this request
+    pub filled_quantity: Quantity,
+    /// Quantity left open on the book
+    pub leaves_quantity: Quantity,
+    /// Unfilled quantity cancelled instead of resting (IOC/FOK, market remainders,
+    /// self-trade prevention)
+    pub cancelled_quantity: Quantity,
+    /// Whether a remainder is resting on the book (or waiting for a stop trigger)
+    pub rested: bool,
+    /// Trades generated by this request
+    pub trades: Vec<Trade>,
+    /// Resting orders cancelled as a side effect (e.g. self-trade prevention,
+    /// the other leg of a one-cancels-other pair)
+    pub cancelled_orders: Vec<OrderId>,
+    /// Conditional orders (stops, bracket exits) triggered by this request, in
+    /// the order they fired
+    pub triggered: Vec<ExecutionReport>,
+    /// Pegged orders repriced because this request moved the best bid/offer,
+    /// one entry per order (a pegged order's own reprices are merged into its
+    /// report instead)
+    pub repriced: Vec<ExecutionReport>,
+}
+
+impl ExecutionReport {
+    /// Report for an order that nothing has happened to yet
+    pub fn new(order_id: OrderId, timestamp: u64) -> Self {
+        ExecutionReport {
+            order_id,
+            timestamp,
+            filled_quantity: 0,
+            leaves_quantity: 0,
+            cancelled_quantity: 0,
+            rested: false,
+            trades: Vec::new(),
+            cancelled_orders: Vec::new(),
+            triggered: Vec::new(),
+            repriced: Vec::new(),
+        }
+    }
+    /// Fold a later execution of the same order (e.g. after a peg reprice)
+    /// into this report
+    pub(crate) fn merge(&mut self, later: ExecutionReport) {
+        self.filled_quantity += later.filled_quantity;
+        self.leaves_quantity = later.leaves_quantity;
+        self.cancelled_quantity += later.cancelled_quantity;
+        self.rested = later.rested;
+        self.trades.extend(later.trades);
+        self.cancelled_orders.extend(later.cancelled_orders);
+        self.triggered.extend(later.triggered);
+        self.repriced.extend(later.repriced);
+    }
+}
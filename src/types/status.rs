@@ -0,0 +1,48 @@
+use super::{OrderId, Price, Quantity, Side, Symbol};
+
+/// Lifecycle state of an order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderState {
+    /// Live with nothing filled yet (resting, or waiting for its stop trigger)
+    New,
+    /// Live with part of its quantity filled
+    PartiallyFilled,
+    /// Entire quantity filled
+    Filled,
+    /// Cancelled by request, or its remainder cancelled (IOC/FOK, market
+    /// remainders, self-trade prevention, order groups)
+    Cancelled,
+    /// Removed by an expiry sweep (DAY/GTD)
+    Expired,
+    /// Assigned an ID but never accepted (e.g. the first leg of an OCO pair
+    /// whose second leg failed validation)
+    Rejected,
+}
+
+impl OrderState {
+    /// Check if the order is finished and can no longer trade
+    pub fn is_terminal(&self) -> bool {
+        !matches!(self, OrderState::New | OrderState::PartiallyFilled)
+    }
+}
+
+/// Snapshot of an order's state, as returned by `MatchingEngine::order_status`
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderStatus {
+    pub order_id: OrderId,
+    pub symbol: Symbol,
+    pub side: Side,
+    pub state: OrderState,
+    /// Quantity the order was submitted with
+    pub original_quantity: Quantity,
+    /// Open quantity, including any iceberg reserve (zero once finished)
+    pub remaining_quantity: Quantity,
+    /// Total quantity filled so far
+    pub filled_quantity: Quantity,
+    /// Volume-weighted average fill price, if anything filled
+    pub average_fill_price: Option<f64>,
+    /// Price level the order rests at (None if not on the book)
+    pub price: Option<Price>,
+    /// Number of orders ahead of it at its price level (None if not on the book)
+    pub queue_position: Option<usize>,
+}
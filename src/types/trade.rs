@@ -1,28 +1,50 @@
-use super::{OrderId, Price, Quantity};
+use super::{Order, OrderId, Price, Quantity, Side, Symbol};
 use std::fmt;
 
+/// Unique identifier for a trade
+pub type TradeId = u64;
+
 /// Represents a trade that occurred when two orders matched
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Trade {
+    pub trade_id: TradeId,
+    pub symbol: Symbol,
     pub buyer_order_id: OrderId,
     pub seller_order_id: OrderId,
+    /// Resting order that provided liquidity
+    pub maker_order_id: OrderId,
+    /// Incoming order that took liquidity
+    pub taker_order_id: OrderId,
+    /// Side of the taker (aggressor)
+    pub aggressor_side: Side,
     pub price: Price,
     pub quantity: Quantity,
     pub timestamp: u64,
 }
 
 impl Trade {
-    /// Create a new trade
+    /// Create a new trade between an incoming (taker) order and a resting (maker) order
     pub fn new(
-        buyer_order_id: OrderId,
-        seller_order_id: OrderId,
+        trade_id: TradeId,
+        taker: &Order,
+        maker_order_id: OrderId,
         price: Price,
         quantity: Quantity,
         timestamp: u64,
     ) -> Self {
+        let (buyer_order_id, seller_order_id) = match taker.side {
+            Side::Buy => (taker.id, maker_order_id),
+            Side::Sell => (maker_order_id, taker.id),
+        };
+
         Trade {
+            trade_id,
+            symbol: taker.symbol.clone(),
             buyer_order_id,
             seller_order_id,
+            maker_order_id,
+            taker_order_id: taker.id,
+            aggressor_side: taker.side,
             price,
             quantity,
             timestamp,
@@ -34,8 +56,14 @@ impl fmt::Display for Trade {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "Trade: {} shares @ {} (Buy Order #{}, Sell Order #{})",
-            self.quantity, self.price, self.buyer_order_id, self.seller_order_id
+            "Trade #{} {}: {} shares @ {} (Buy Order #{}, Sell Order #{}, {:?} aggressor)",
+            self.trade_id,
+            self.symbol,
+            self.quantity,
+            self.price,
+            self.buyer_order_id,
+            self.seller_order_id,
+            self.aggressor_side
         )
     }
-}
\ No newline at end of file
+}
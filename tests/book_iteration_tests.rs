@@ -0,0 +1,62 @@
+use rustex::{MatchingEngine, Order, Side};
+
+#[test]
+fn test_orders_in_priority_order() {
+    let mut engine = MatchingEngine::new();
+
+    let a = engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15000, 100)).unwrap();
+    let b = engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15010, 50)).unwrap();
+    let c = engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15000, 70)).unwrap();
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15100, 30)).unwrap();
+
+    let book = engine.get_order_book("AAPL").unwrap();
+    let bids: Vec<_> = book.orders(Side::Buy).collect();
+    let ids: Vec<u64> = bids.iter().map(|o| o.order_id).collect();
+    assert_eq!(ids, vec![b.order_id, a.order_id, c.order_id], "Best price first, then time");
+
+    assert_eq!(bids[0].queue_position, 0);
+    assert_eq!(bids[1].queue_position, 0, "Position is within the price level");
+    assert_eq!(bids[2].queue_position, 1);
+    assert_eq!(bids[2].price, 15000);
+    assert_eq!(bids[2].quantity, 70);
+    assert_eq!(bids[2].timestamp, c.timestamp);
+    assert!(bids.iter().all(|o| o.side == Side::Buy));
+
+    let asks: Vec<_> = book.orders(Side::Sell).collect();
+    assert_eq!(asks.len(), 1);
+    assert_eq!(asks[0].price, 15100);
+}
+
+#[test]
+fn test_orders_include_hidden_and_iceberg_reserve() {
+    let mut engine = MatchingEngine::new();
+
+    let hidden = engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15000, 40).with_hidden(true)).unwrap();
+    let iceberg = engine.submit_order(
+        Order::limit("AAPL".to_string(), Side::Sell, 15000, 100).with_display_quantity(10),
+    ).unwrap();
+
+    let asks: Vec<_> = engine.get_order_book("AAPL").unwrap().orders(Side::Sell).collect();
+    assert_eq!(asks[0].order_id, iceberg.order_id, "Displayed orders rank ahead of hidden ones");
+    assert_eq!(asks[0].quantity, 100);
+    assert_eq!(asks[0].displayed_quantity, 10);
+    assert_eq!(asks[1].order_id, hidden.order_id);
+    assert_eq!(asks[1].displayed_quantity, 0);
+    assert_eq!(asks[1].queue_position, 1);
+}
+
+#[test]
+fn test_orders_reflect_fills_through_shared_reference() {
+    let mut engine = MatchingEngine::new();
+
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15000, 100)).unwrap();
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 14900, 100)).unwrap();
+    engine.submit_order(Order::market("AAPL".to_string(), Side::Sell, 130)).unwrap();
+
+    // Read-only access is enough
+    let engine = &engine;
+    let bids: Vec<_> = engine.get_order_book("AAPL").unwrap().orders(Side::Buy).collect();
+    assert_eq!(bids.len(), 1);
+    assert_eq!(bids[0].price, 14900);
+    assert_eq!(bids[0].quantity, 70);
+}
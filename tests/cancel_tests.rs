@@ -97,7 +97,7 @@ fn test_cancel_after_partial_fill() {
     
     // Partially fill it
     let sell = Order::limit("AAPL".to_string(), Side::Sell, 15000, 75);
-    let trades = engine.submit_order(sell).unwrap();
+    let trades = engine.submit_order(sell).unwrap().trades;
     assert_eq!(trades.len(), 1);
     assert_eq!(trades[0].quantity, 75);
     
@@ -120,7 +120,7 @@ fn test_cannot_cancel_fully_filled_order() {
     
     // Fully fill it
     let sell = Order::limit("AAPL".to_string(), Side::Sell, 15000, 100);
-    let trades = engine.submit_order(sell).unwrap();
+    let trades = engine.submit_order(sell).unwrap().trades;
     assert_eq!(trades[0].quantity, 100);
     
     // Try to cancel (should fail - order is gone)
@@ -142,9 +142,43 @@ fn test_cancel_maintains_price_time_priority() {
     
     // Now match - should match with order #2 (now first in line)
     let sell = Order::limit("AAPL".to_string(), Side::Sell, 15000, 35);
-    let trades = engine.submit_order(sell).unwrap();
+    let trades = engine.submit_order(sell).unwrap().trades;
     
     assert_eq!(trades.len(), 1);
     assert_eq!(trades[0].buyer_order_id, 2, "Should match with order #2");
     assert_eq!(trades[0].quantity, 35);
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_cancel_by_id_without_symbol() {
+    let mut engine = MatchingEngine::new();
+
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15000, 100)).unwrap();
+    let msft = engine.submit_order(Order::limit("MSFT".to_string(), Side::Sell, 30000, 100)).unwrap();
+    let stop = engine.submit_order(Order::stop("AAPL".to_string(), Side::Sell, 14000, 100)).unwrap();
+
+    assert_eq!(engine.order_symbol(msft.order_id).map(String::as_str), Some("MSFT"));
+    assert!(engine.cancel_order_by_id(msft.order_id));
+    assert!(engine.cancel_order_by_id(stop.order_id), "Pending stops are found too");
+
+    let book = engine.get_order_book("MSFT").unwrap();
+    assert!(book.best_ask().is_none());
+    assert!(!engine.cancel_order_by_id(msft.order_id), "Already cancelled");
+    assert!(engine.order_symbol(msft.order_id).is_none());
+}
+
+#[test]
+fn test_order_index_drops_finished_orders() {
+    let mut engine = MatchingEngine::new();
+
+    let maker = engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15000, 100)).unwrap();
+    let taker = engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15000, 60)).unwrap();
+
+    // Fully filled taker never rests; partially filled maker is still live
+    assert!(engine.get_order(taker.order_id).is_none());
+    assert_eq!(engine.get_order(maker.order_id).map(|o| o.quantity), Some(40));
+
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15000, 40)).unwrap();
+    assert!(engine.get_order(maker.order_id).is_none(), "Filled maker leaves the index");
+    assert!(!engine.cancel_order_by_id(maker.order_id));
+}
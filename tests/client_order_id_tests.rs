@@ -0,0 +1,71 @@
+use rustex::{MatchingEngine, Order, OrderError, Side};
+
+fn order(owner: &str, client_order_id: &str, price: u64) -> Order {
+    Order::limit("AAPL".to_string(), Side::Buy, price, 100)
+        .with_owner(owner.to_string())
+        .with_client_order_id(client_order_id.to_string())
+}
+
+#[test]
+fn test_reject_duplicate_live_client_id() {
+    let mut engine = MatchingEngine::new();
+
+    let first = engine.submit_order(order("acct1", "ABC-1", 15000)).unwrap();
+    assert_eq!(engine.order_id_for_client_id(Some("acct1"), "ABC-1"), Some(first.order_id));
+
+    assert_eq!(
+        engine.submit_order(order("acct1", "ABC-1", 14900)).unwrap_err(),
+        OrderError::DuplicateClientOrderId
+    );
+
+    // Unique per account: another account can use the same ID
+    assert!(engine.submit_order(order("acct2", "ABC-1", 14900)).is_ok());
+}
+
+#[test]
+fn test_client_id_reusable_once_order_is_done() {
+    let mut engine = MatchingEngine::new();
+
+    let first = engine.submit_order(order("acct1", "ABC-1", 15000)).unwrap();
+    assert!(engine.cancel_order_by_client_id(Some("acct1"), "ABC-1"));
+    assert!(engine.order_id_for_client_id(Some("acct1"), "ABC-1").is_none());
+
+    let second = engine.submit_order(order("acct1", "ABC-1", 15000)).unwrap();
+    assert_ne!(second.order_id, first.order_id);
+
+    // Fully filled orders release their client ID too
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15000, 100)).unwrap();
+    assert!(engine.order_id_for_client_id(Some("acct1"), "ABC-1").is_none());
+    assert!(engine.submit_order(order("acct1", "ABC-1", 15000)).is_ok());
+}
+
+#[test]
+fn test_cancel_by_client_id_needs_matching_owner() {
+    let mut engine = MatchingEngine::new();
+
+    engine.submit_order(order("acct1", "ABC-1", 15000)).unwrap();
+
+    assert!(!engine.cancel_order_by_client_id(Some("acct2"), "ABC-1"), "Other account's ID space");
+    assert!(!engine.cancel_order_by_client_id(None, "ABC-1"));
+    assert!(engine.cancel_order_by_client_id(Some("acct1"), "ABC-1"));
+    assert!(!engine.cancel_order_by_client_id(Some("acct1"), "ABC-1"), "Already cancelled");
+}
+
+#[test]
+fn test_modify_by_client_id() {
+    let mut engine = MatchingEngine::new();
+
+    let original = engine.submit_order(order("acct1", "ABC-1", 15000)).unwrap();
+
+    let report = engine.modify_order_by_client_id(Some("acct1"), "ABC-1", 15010, 80).unwrap();
+    assert_eq!(report.order_id, original.order_id);
+    assert_eq!(engine.order_id_for_client_id(Some("acct1"), "ABC-1"), Some(original.order_id), "Amend keeps the client ID");
+    if let Some(book) = engine.get_order_book("AAPL") {
+        assert_eq!(book.best_bid(), Some(15010));
+    }
+
+    assert_eq!(
+        engine.modify_order_by_client_id(Some("acct1"), "XYZ-9", 15000, 10),
+        Err(OrderError::ClientOrderIdNotFound("XYZ-9".to_string()))
+    );
+}
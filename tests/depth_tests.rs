@@ -0,0 +1,66 @@
+use rustex::orderbook::DepthLevel;
+use rustex::{MatchingEngine, Order, Side};
+
+#[test]
+fn test_depth_aggregates_levels_best_first() {
+    let mut engine = MatchingEngine::new();
+
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15000, 100)).unwrap();
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15000, 50)).unwrap();
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 14900, 70)).unwrap();
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15100, 30)).unwrap();
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15200, 40)).unwrap();
+
+    let depth = engine.get_order_book("AAPL").unwrap().depth(10);
+    assert_eq!(depth.bids, vec![
+        DepthLevel { price: 15000, quantity: 150, order_count: 2 },
+        DepthLevel { price: 14900, quantity: 70, order_count: 1 },
+    ]);
+    assert_eq!(depth.asks, vec![
+        DepthLevel { price: 15100, quantity: 30, order_count: 1 },
+        DepthLevel { price: 15200, quantity: 40, order_count: 1 },
+    ]);
+}
+
+#[test]
+fn test_depth_limits_levels_per_side() {
+    let mut engine = MatchingEngine::new();
+
+    for i in 0..5 {
+        engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15000 - i * 10, 10)).unwrap();
+        engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15100 + i * 10, 10)).unwrap();
+    }
+
+    let depth = engine.get_order_book("AAPL").unwrap().depth(3);
+    assert_eq!(depth.bids.len(), 3);
+    assert_eq!(depth.asks.len(), 3);
+    assert_eq!(depth.bids[2].price, 14980);
+    assert_eq!(depth.asks[2].price, 15120);
+
+    assert!(engine.get_order_book("AAPL").unwrap().depth(0).bids.is_empty());
+}
+
+#[test]
+fn test_depth_shows_only_displayed_quantity() {
+    let mut engine = MatchingEngine::new();
+
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15000, 100).with_display_quantity(10)).unwrap();
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15000, 50).with_hidden(true)).unwrap();
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15010, 50).with_hidden(true)).unwrap();
+
+    let depth = engine.get_order_book("AAPL").unwrap().depth(5);
+    assert_eq!(depth.bids, vec![DepthLevel { price: 15000, quantity: 10, order_count: 1 }], "Only the iceberg peak shows");
+    assert!(depth.asks.is_empty());
+}
+
+#[test]
+fn test_depth_updates_after_fills() {
+    let mut engine = MatchingEngine::new();
+
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15000, 50)).unwrap();
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15100, 50)).unwrap();
+    engine.submit_order(Order::market("AAPL".to_string(), Side::Buy, 70)).unwrap();
+
+    let depth = engine.get_order_book("AAPL").unwrap().depth(5);
+    assert_eq!(depth.asks, vec![DepthLevel { price: 15100, quantity: 30, order_count: 1 }]);
+}
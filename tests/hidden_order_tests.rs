@@ -0,0 +1,100 @@
+use rustex::{MatchingEngine, Order, OrderError, PostOnly, Side};
+
+#[test]
+fn test_hidden_order_not_displayed_but_matches() {
+    let mut engine = MatchingEngine::new();
+
+    let hidden = engine.submit_order(
+        Order::limit("AAPL".to_string(), Side::Buy, 15000, 100).with_hidden(true),
+    ).unwrap();
+    assert!(hidden.rested);
+
+    if let Some(book) = engine.get_order_book("AAPL") {
+        assert!(book.best_bid().is_none(), "Hidden orders don't show in the BBO");
+        assert_eq!(book.best_executable_price(Side::Buy), Some(15000));
+        book.check_invariants().unwrap();
+    }
+
+    let trades = engine.submit_order(Order::market("AAPL".to_string(), Side::Sell, 60)).unwrap().trades;
+    assert_eq!(trades.len(), 1);
+    assert_eq!(trades[0].buyer_order_id, hidden.order_id);
+    assert_eq!(trades[0].quantity, 60);
+}
+
+#[test]
+fn test_displayed_orders_have_priority_at_same_price() {
+    let mut engine = MatchingEngine::new();
+
+    // Hidden order arrives first but still queues behind displayed orders
+    let hidden = engine.submit_order(
+        Order::limit("AAPL".to_string(), Side::Sell, 15000, 50).with_hidden(true),
+    ).unwrap();
+    let displayed = engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15000, 50)).unwrap();
+
+    let trades = engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15000, 70)).unwrap().trades;
+    assert_eq!(trades.len(), 2);
+    assert_eq!(trades[0].seller_order_id, displayed.order_id);
+    assert_eq!(trades[0].quantity, 50);
+    assert_eq!(trades[1].seller_order_id, hidden.order_id);
+    assert_eq!(trades[1].quantity, 20);
+}
+
+#[test]
+fn test_price_priority_beats_display_priority() {
+    let mut engine = MatchingEngine::new();
+
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15100, 50)).unwrap();
+    let hidden = engine.submit_order(
+        Order::limit("AAPL".to_string(), Side::Sell, 15000, 50).with_hidden(true),
+    ).unwrap();
+
+    if let Some(book) = engine.get_order_book("AAPL") {
+        assert_eq!(book.best_ask(), Some(15100), "Displayed BBO skips the hidden level");
+    }
+
+    let trades = engine.submit_order(Order::market("AAPL".to_string(), Side::Buy, 10)).unwrap().trades;
+    assert_eq!(trades[0].seller_order_id, hidden.order_id, "Better-priced hidden order fills first");
+    assert_eq!(trades[0].price, 15000);
+}
+
+#[test]
+fn test_post_only_respects_hidden_liquidity() {
+    let mut engine = MatchingEngine::new();
+
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15000, 50).with_hidden(true)).unwrap();
+
+    let buy = Order::limit("AAPL".to_string(), Side::Buy, 15000, 10).with_post_only(PostOnly::Reject);
+    assert_eq!(engine.submit_order(buy).unwrap_err(), OrderError::WouldTakeLiquidity);
+
+    let buy = Order::limit("AAPL".to_string(), Side::Buy, 15000, 10).with_post_only(PostOnly::Slide);
+    let report = engine.submit_order(buy).unwrap();
+    assert!(report.trades.is_empty(), "Post-only must not hit hidden orders");
+    if let Some(book) = engine.get_order_book("AAPL") {
+        assert_eq!(book.best_bid(), Some(14999));
+    }
+}
+
+#[test]
+fn test_cancel_hidden_order() {
+    let mut engine = MatchingEngine::new();
+
+    let hidden = engine.submit_order(
+        Order::limit("AAPL".to_string(), Side::Buy, 15000, 100).with_hidden(true),
+    ).unwrap();
+    assert!(engine.cancel_order("AAPL", hidden.order_id));
+
+    if let Some(book) = engine.get_order_book("AAPL") {
+        assert!(book.best_executable_price(Side::Buy).is_none());
+        book.check_invariants().unwrap();
+    }
+}
+
+#[test]
+fn test_reject_hidden_iceberg() {
+    let mut engine = MatchingEngine::new();
+
+    let order = Order::limit("AAPL".to_string(), Side::Buy, 15000, 100)
+        .with_hidden(true)
+        .with_display_quantity(10);
+    assert_eq!(engine.submit_order(order).unwrap_err(), OrderError::InvalidDisplayQuantity);
+}
@@ -0,0 +1,109 @@
+use rustex::{MatchingEngine, Order, OrderError, Side, TimeInForce};
+
+#[test]
+fn test_iceberg_only_shows_peak() {
+    let mut engine = MatchingEngine::new();
+
+    let iceberg = Order::limit("AAPL".to_string(), Side::Sell, 15000, 100).with_display_quantity(20);
+    let report = engine.submit_order(iceberg).unwrap();
+    assert_eq!(report.leaves_quantity, 100, "Full size is open");
+
+    if let Some(book) = engine.get_order_book("AAPL") {
+        let order = book.get_order(report.order_id).unwrap();
+        assert_eq!(order.quantity, 20, "Only the peak is displayed");
+        assert_eq!(order.reserve_quantity, 80);
+        assert_eq!(order.leaves_quantity(), 100);
+        book.check_invariants().unwrap();
+    }
+}
+
+#[test]
+fn test_peak_refresh_loses_priority() {
+    let mut engine = MatchingEngine::new();
+
+    let iceberg = engine.submit_order(
+        Order::limit("AAPL".to_string(), Side::Sell, 15000, 100).with_display_quantity(30),
+    ).unwrap();
+    let regular = engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15000, 50)).unwrap();
+
+    // Takes the iceberg's peak, then moves on to the regular order
+    let trades = engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15000, 40)).unwrap().trades;
+    assert_eq!(trades.len(), 2);
+    assert_eq!(trades[0].seller_order_id, iceberg.order_id);
+    assert_eq!(trades[0].quantity, 30);
+    assert_eq!(trades[1].seller_order_id, regular.order_id, "Refreshed peak goes behind the regular order");
+    assert_eq!(trades[1].quantity, 10);
+
+    if let Some(book) = engine.get_order_book("AAPL") {
+        let order = book.get_order(iceberg.order_id).unwrap();
+        assert_eq!(order.quantity, 30, "Peak refreshed from reserve");
+        assert_eq!(order.reserve_quantity, 40);
+        book.check_invariants().unwrap();
+    }
+}
+
+#[test]
+fn test_large_taker_consumes_iceberg_across_refreshes() {
+    let mut engine = MatchingEngine::new();
+
+    let iceberg = engine.submit_order(
+        Order::limit("AAPL".to_string(), Side::Buy, 15000, 70).with_display_quantity(30),
+    ).unwrap();
+
+    let report = engine.submit_order(Order::market("AAPL".to_string(), Side::Sell, 100)).unwrap();
+    let quantities: Vec<u64> = report.trades.iter().map(|t| t.quantity).collect();
+    assert_eq!(quantities, vec![30, 30, 10], "Each fill is capped at the displayed peak");
+    assert!(report.trades.iter().all(|t| t.buyer_order_id == iceberg.order_id));
+    assert_eq!(report.cancelled_quantity, 30);
+
+    if let Some(book) = engine.get_order_book("AAPL") {
+        assert!(book.best_bid().is_none(), "Iceberg fully consumed");
+        book.check_invariants().unwrap();
+    }
+}
+
+#[test]
+fn test_fok_counts_hidden_reserve() {
+    let mut engine = MatchingEngine::new();
+
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15000, 100).with_display_quantity(10)).unwrap();
+
+    let fok = Order::limit("AAPL".to_string(), Side::Buy, 15000, 100)
+        .with_time_in_force(TimeInForce::FillOrKill);
+    let report = engine.submit_order(fok).unwrap();
+    assert_eq!(report.filled_quantity, 100, "Reserve is available liquidity");
+}
+
+#[test]
+fn test_reduce_iceberg_takes_from_reserve_first() {
+    let mut engine = MatchingEngine::new();
+
+    let iceberg = engine.submit_order(
+        Order::limit("AAPL".to_string(), Side::Sell, 15000, 100).with_display_quantity(20),
+    ).unwrap();
+
+    engine.modify_order("AAPL", iceberg.order_id, 15000, 30).unwrap();
+    if let Some(book) = engine.get_order_book("AAPL") {
+        let order = book.get_order(iceberg.order_id).unwrap();
+        assert_eq!(order.quantity, 20, "Displayed peak unchanged");
+        assert_eq!(order.reserve_quantity, 10);
+    }
+
+    // Cancel reports nothing left behind
+    assert!(engine.cancel_order("AAPL", iceberg.order_id));
+    if let Some(book) = engine.get_order_book("AAPL") {
+        assert!(book.best_ask().is_none());
+        book.check_invariants().unwrap();
+    }
+}
+
+#[test]
+fn test_reject_invalid_display_quantity() {
+    let mut engine = MatchingEngine::new();
+
+    let zero = Order::limit("AAPL".to_string(), Side::Buy, 15000, 100).with_display_quantity(0);
+    assert_eq!(engine.submit_order(zero).unwrap_err(), OrderError::InvalidDisplayQuantity);
+
+    let market = Order::market("AAPL".to_string(), Side::Buy, 100).with_display_quantity(10);
+    assert_eq!(engine.submit_order(market).unwrap_err(), OrderError::InvalidDisplayQuantity);
+}
@@ -0,0 +1,104 @@
+use rustex::{MatchingEngine, Order, Side, TimeInForce};
+
+fn assert_book_consistent(engine: &MatchingEngine, symbol: &str) {
+    if let Some(book) = engine.get_order_book(symbol) {
+        if let Err(e) = book.check_invariants() {
+            panic!("Book invariants violated for {}: {}", symbol, e);
+        }
+    }
+}
+
+#[test]
+fn test_full_fill_removes_location_entry() {
+    let mut engine = MatchingEngine::new();
+
+    let maker = engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15000, 100)).unwrap();
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15000, 100)).unwrap();
+
+    // Filled maker must not leave a stale index entry behind
+    assert_book_consistent(&engine, "AAPL");
+    assert!(!engine.cancel_order("AAPL", maker.order_id), "Filled order cannot be cancelled");
+}
+
+#[test]
+fn test_sweep_across_levels_keeps_index_consistent() {
+    let mut engine = MatchingEngine::new();
+
+    for i in 0..5 {
+        engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15000 - i * 10, 20)).unwrap();
+    }
+
+    // Fills four makers completely and one partially
+    let report = engine.submit_order(Order::market("AAPL".to_string(), Side::Sell, 90)).unwrap();
+    assert_eq!(report.trades.len(), 5);
+    assert_book_consistent(&engine, "AAPL");
+
+    if let Some(book) = engine.get_order_book("AAPL") {
+        assert_eq!(book.best_bid(), Some(14960), "Partially filled maker remains");
+    }
+}
+
+#[test]
+fn test_invariants_hold_after_mixed_operations() {
+    let mut engine = MatchingEngine::new();
+    let mut seed: u64 = 42;
+    let mut next = || {
+        // Simple LCG so the sequence is deterministic
+        seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        seed >> 33
+    };
+
+    let mut live = Vec::new();
+    for _ in 0..500 {
+        match next() % 5 {
+            0 | 1 => {
+                let side = if next() % 2 == 0 { Side::Buy } else { Side::Sell };
+                let price = 14950 + next() % 100;
+                let order = Order::limit("AAPL".to_string(), side, price, 1 + next() % 50);
+                let report = engine.submit_order(order).unwrap();
+                if report.rested {
+                    live.push(report.order_id);
+                }
+            }
+            2 => {
+                let side = if next() % 2 == 0 { Side::Buy } else { Side::Sell };
+                let order = Order::market("AAPL".to_string(), side, 1 + next() % 80);
+                engine.submit_order(order).unwrap();
+            }
+            3 => {
+                if !live.is_empty() {
+                    let id = live.swap_remove((next() as usize) % live.len());
+                    engine.cancel_order("AAPL", id);
+                }
+            }
+            _ => {
+                let side = if next() % 2 == 0 { Side::Buy } else { Side::Sell };
+                let order = Order::limit("AAPL".to_string(), side, 14950 + next() % 100, 1 + next() % 50)
+                    .with_time_in_force(TimeInForce::ImmediateOrCancel);
+                engine.submit_order(order).unwrap();
+            }
+        }
+        assert_book_consistent(&engine, "AAPL");
+    }
+}
+
+#[test]
+fn test_invariants_hold_after_amend_and_expiry() {
+    let mut engine = MatchingEngine::new();
+
+    let a = engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15000, 100)).unwrap();
+    let b = engine.submit_order(
+        Order::limit("AAPL".to_string(), Side::Sell, 15100, 50).with_time_in_force(TimeInForce::Day),
+    ).unwrap();
+
+    engine.modify_order("AAPL", a.order_id, 15000, 60).unwrap();
+    assert_book_consistent(&engine, "AAPL");
+
+    // Amend through the ask, filling the DAY order completely
+    engine.modify_order("AAPL", a.order_id, 15100, 60).unwrap();
+    assert_book_consistent(&engine, "AAPL");
+    assert!(!engine.cancel_order("AAPL", b.order_id));
+
+    engine.expire_orders(u64::MAX);
+    assert_book_consistent(&engine, "AAPL");
+}
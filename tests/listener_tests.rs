@@ -0,0 +1,209 @@
+use rustex::{EngineListener, MatchingEngine, Order, OrderError, SelfTradePrevention, Side, TimeInForce, Trade};
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Clone, PartialEq)]
+enum Event {
+    Accept(u64),
+    Reject(OrderError),
+    AmendReject(u64, OrderError),
+    Fill(u64, u64, u64),
+    PartialFill(u64, u64, u64, u64),
+    Cancel(u64, u64),
+    Expire(u64, u64),
+    Trade(u64, u64, u64),
+    Bbo(String, Option<u64>, Option<u64>),
+}
+
+/// Listener that records every callback into a shared log
+struct Recorder(Arc<Mutex<Vec<Event>>>);
+
+impl EngineListener for Recorder {
+    fn on_accept(&mut self, order: &Order) {
+        self.0.lock().unwrap().push(Event::Accept(order.id));
+    }
+
+    fn on_reject(&mut self, _order: &Order, reason: &OrderError) {
+        self.0.lock().unwrap().push(Event::Reject(reason.clone()));
+    }
+
+    fn on_amend_reject(&mut self, order_id: u64, reason: &OrderError) {
+        self.0.lock().unwrap().push(Event::AmendReject(order_id, reason.clone()));
+    }
+
+    fn on_fill(&mut self, order_id: u64, price: u64, quantity: u64) {
+        self.0.lock().unwrap().push(Event::Fill(order_id, price, quantity));
+    }
+
+    fn on_partial_fill(&mut self, order_id: u64, price: u64, quantity: u64, leaves_quantity: u64) {
+        self.0.lock().unwrap().push(Event::PartialFill(order_id, price, quantity, leaves_quantity));
+    }
+
+    fn on_cancel(&mut self, order_id: u64, cancelled_quantity: u64) {
+        self.0.lock().unwrap().push(Event::Cancel(order_id, cancelled_quantity));
+    }
+
+    fn on_expire(&mut self, order_id: u64, expired_quantity: u64) {
+        self.0.lock().unwrap().push(Event::Expire(order_id, expired_quantity));
+    }
+
+    fn on_trade(&mut self, trade: &Trade) {
+        self.0.lock().unwrap().push(Event::Trade(trade.trade_id, trade.price, trade.quantity));
+    }
+
+    fn on_bbo_change(&mut self, symbol: &str, best_bid: Option<u64>, best_ask: Option<u64>) {
+        self.0.lock().unwrap().push(Event::Bbo(symbol.to_string(), best_bid, best_ask));
+    }
+}
+
+fn engine_with_recorder() -> (MatchingEngine, Arc<Mutex<Vec<Event>>>) {
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let mut engine = MatchingEngine::new();
+    engine.add_listener(Box::new(Recorder(log.clone())));
+    (engine, log)
+}
+
+fn drain(log: &Arc<Mutex<Vec<Event>>>) -> Vec<Event> {
+    std::mem::take(&mut *log.lock().unwrap())
+}
+
+#[test]
+fn test_accept_trade_and_fills() {
+    let (mut engine, log) = engine_with_recorder();
+
+    let sell = engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15000, 100)).unwrap();
+    let buy = engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15000, 40)).unwrap();
+
+    assert_eq!(drain(&log), vec![
+        Event::Accept(sell.order_id),
+        Event::Bbo("AAPL".to_string(), None, Some(15000)),
+        Event::Accept(buy.order_id),
+        Event::Trade(1, 15000, 40),
+        Event::Fill(buy.order_id, 15000, 40),
+        Event::PartialFill(sell.order_id, 15000, 40, 60),
+    ]);
+}
+
+#[test]
+fn test_rejections() {
+    let (mut engine, log) = engine_with_recorder();
+
+    let result = engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15000, 0));
+    assert!(result.is_err());
+
+    let result = engine.submit_oco(
+        Order::limit("AAPL".to_string(), Side::Sell, 15100, 10),
+        Order::market("AAPL".to_string(), Side::Sell, 10),
+    );
+    assert!(result.is_err());
+
+    assert_eq!(drain(&log), vec![
+        Event::Reject(OrderError::ZeroQuantity),
+        Event::Reject(OrderError::InvalidOrderGroup),
+    ]);
+}
+
+#[test]
+fn test_amend_rejections() {
+    let (mut engine, log) = engine_with_recorder();
+
+    let sell = engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15000, 100)).unwrap();
+    drain(&log);
+
+    assert!(engine.modify_order("AAPL", sell.order_id, 15000, 0).is_err());
+    assert!(engine.modify_order("AAPL", 999, 15000, 50).is_err());
+    assert!(engine.modify_order_by_id(999, 15000, 50).is_err());
+    assert!(engine.modify_order_by_client_id(None, "missing", 15000, 50).is_err());
+
+    assert_eq!(drain(&log), vec![
+        Event::AmendReject(sell.order_id, OrderError::ZeroQuantity),
+        Event::AmendReject(999, OrderError::OrderNotFound(999)),
+        Event::AmendReject(999, OrderError::OrderNotFound(999)),
+        Event::AmendReject(0, OrderError::ClientOrderIdNotFound("missing".to_string())),
+    ]);
+}
+
+#[test]
+fn test_cancel_reports_oco_sibling() {
+    let (mut engine, log) = engine_with_recorder();
+
+    let (first, second) = engine.submit_oco(
+        Order::limit("AAPL".to_string(), Side::Buy, 14900, 10),
+        Order::stop("AAPL".to_string(), Side::Buy, 15200, 10),
+    ).unwrap();
+    drain(&log);
+
+    assert!(engine.cancel_order("AAPL", first.order_id));
+    let events = drain(&log);
+    assert!(events.contains(&Event::Cancel(first.order_id, 10)));
+    assert!(events.contains(&Event::Cancel(second.order_id, 10)), "OCO sibling cancel is reported");
+}
+
+#[test]
+fn test_ioc_remainder_cancel_and_expiry() {
+    let (mut engine, log) = engine_with_recorder();
+
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15000, 30)).unwrap();
+    let day = engine.submit_order(
+        Order::limit("AAPL".to_string(), Side::Sell, 15100, 20).with_time_in_force(TimeInForce::Day),
+    ).unwrap();
+    let ioc = engine.submit_order(
+        Order::limit("AAPL".to_string(), Side::Buy, 15000, 50).with_time_in_force(TimeInForce::ImmediateOrCancel),
+    ).unwrap();
+    assert!(drain(&log).contains(&Event::Cancel(ioc.order_id, 20)));
+
+    engine.expire_orders(u64::MAX);
+    assert_eq!(drain(&log), vec![
+        Event::Expire(day.order_id, 20),
+        Event::Bbo("AAPL".to_string(), None, None),
+    ]);
+}
+
+#[test]
+fn test_self_trade_decrement_reports_both_sides() {
+    let (mut engine, log) = engine_with_recorder();
+    let owned = |side, quantity| {
+        Order::limit("AAPL".to_string(), side, 15000, quantity)
+            .with_owner("MM1".to_string())
+            .with_self_trade_prevention(SelfTradePrevention::DecrementAndCancel)
+    };
+
+    let resting = engine.submit_order(owned(Side::Sell, 10)).unwrap();
+    drain(&log);
+
+    // Resting order survives at 6; the incoming order is used up
+    let incoming = engine.submit_order(owned(Side::Buy, 4)).unwrap();
+    let events = drain(&log);
+    assert!(events.contains(&Event::Cancel(resting.order_id, 4)), "Decrement of a surviving order is reported");
+    assert!(events.contains(&Event::Cancel(incoming.order_id, 4)));
+}
+
+#[test]
+fn test_bbo_changes() {
+    let (mut engine, log) = engine_with_recorder();
+
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 14900, 10)).unwrap();
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 14800, 10)).unwrap();  // Behind the best bid
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 14950, 10).with_hidden(true)).unwrap();  // Not displayed
+    let ask = engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15000, 10)).unwrap();
+    engine.cancel_order("AAPL", ask.order_id);
+
+    let bbos: Vec<Event> = drain(&log).into_iter().filter(|e| matches!(e, Event::Bbo(..))).collect();
+    assert_eq!(bbos, vec![
+        Event::Bbo("AAPL".to_string(), Some(14900), None),
+        Event::Bbo("AAPL".to_string(), Some(14900), Some(15000)),
+        Event::Bbo("AAPL".to_string(), Some(14900), None),
+    ]);
+}
+
+#[test]
+fn test_multiple_listeners() {
+    let (mut engine, first) = engine_with_recorder();
+    let second = Arc::new(Mutex::new(Vec::new()));
+    engine.add_listener(Box::new(Recorder(second.clone())));
+
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15000, 10)).unwrap();
+
+    let events = drain(&first);
+    assert_eq!(events.len(), 2);
+    assert_eq!(events, drain(&second));
+}
@@ -0,0 +1,142 @@
+use rustex::orderbook::{BookEvent, SequencedEvent};
+use rustex::{MatchingEngine, Order, Side, TimeInForce};
+use std::collections::BTreeMap;
+
+/// Displayed book rebuilt from events: order ID -> (side, price, quantity)
+type Replica = BTreeMap<u64, (Side, u64, u64)>;
+
+fn apply(replica: &mut Replica, events: &[SequencedEvent]) {
+    for event in events {
+        match event.event {
+            BookEvent::OrderAdded { order_id, side, price, quantity } => {
+                replica.insert(order_id, (side, price, quantity));
+            }
+            BookEvent::OrderReduced { order_id, quantity } | BookEvent::OrderExecuted { order_id, quantity, .. } => {
+                let entry = replica.get_mut(&order_id).expect("Event for unknown order");
+                entry.2 -= quantity;
+                if entry.2 == 0 {
+                    replica.remove(&order_id);
+                }
+            }
+            BookEvent::OrderDeleted { order_id } => {
+                replica.remove(&order_id).expect("Delete for unknown order");
+            }
+            BookEvent::TradePrinted { .. } => {}
+        }
+    }
+}
+
+fn snapshot(engine: &MatchingEngine, symbol: &str) -> Replica {
+    let book = engine.get_order_book(symbol).unwrap();
+    book.orders(Side::Buy)
+        .chain(book.orders(Side::Sell))
+        .filter(|o| o.displayed_quantity > 0)
+        .map(|o| (o.order_id, (o.side, o.price, o.displayed_quantity)))
+        .collect()
+}
+
+#[test]
+fn test_events_are_sequenced() {
+    let mut engine = MatchingEngine::new().with_market_data();
+
+    let maker = engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15000, 100)).unwrap();
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15000, 40)).unwrap();
+
+    let events = engine.take_market_data("AAPL");
+    let sequences: Vec<u64> = events.iter().map(|e| e.sequence).collect();
+    assert_eq!(sequences, vec![1, 2, 3]);
+    assert_eq!(events[0].event, BookEvent::OrderAdded { order_id: maker.order_id, side: Side::Sell, price: 15000, quantity: 100 });
+    assert_eq!(events[1].event, BookEvent::OrderExecuted { order_id: maker.order_id, price: 15000, quantity: 40 });
+    assert!(matches!(events[2].event, BookEvent::TradePrinted { price: 15000, quantity: 40, aggressor_side: Side::Buy, .. }));
+
+    assert!(engine.take_market_data("AAPL").is_empty(), "Events are drained");
+    assert_eq!(engine.get_order_book("AAPL").unwrap().sequence(), 3);
+}
+
+#[test]
+fn test_cancel_amend_and_expire_events() {
+    let mut engine = MatchingEngine::new().with_market_data();
+
+    let a = engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15000, 100)).unwrap();
+    let b = engine.submit_order(
+        Order::limit("AAPL".to_string(), Side::Buy, 14900, 50).with_time_in_force(TimeInForce::Day),
+    ).unwrap();
+    engine.take_market_data("AAPL");
+
+    engine.modify_order("AAPL", a.order_id, 15000, 60).unwrap();
+    engine.cancel_order("AAPL", a.order_id);
+    engine.expire_orders(u64::MAX);
+
+    let events: Vec<BookEvent> = engine.take_market_data("AAPL").into_iter().map(|e| e.event).collect();
+    assert_eq!(events, vec![
+        BookEvent::OrderReduced { order_id: a.order_id, quantity: 40 },
+        BookEvent::OrderDeleted { order_id: a.order_id },
+        BookEvent::OrderDeleted { order_id: b.order_id },
+    ]);
+}
+
+#[test]
+fn test_hidden_orders_only_print_trades() {
+    let mut engine = MatchingEngine::new().with_market_data();
+
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15000, 100).with_hidden(true)).unwrap();
+    engine.submit_order(Order::market("AAPL".to_string(), Side::Buy, 30)).unwrap();
+
+    let events = engine.take_market_data("AAPL");
+    assert_eq!(events.len(), 1);
+    assert!(matches!(events[0].event, BookEvent::TradePrinted { quantity: 30, .. }));
+}
+
+#[test]
+fn test_snapshot_plus_deltas_rebuilds_book() {
+    let mut engine = MatchingEngine::new().with_market_data();
+
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15000, 100)).unwrap();
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15100, 300).with_display_quantity(50)).unwrap();
+
+    // Snapshot mid-stream, then only apply what comes after it
+    engine.take_market_data("AAPL");
+    let mut replica = snapshot(&engine, "AAPL");
+
+    let mut seed: u64 = 7;
+    let mut next = || {
+        seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        seed >> 33
+    };
+    let mut live = Vec::new();
+    for _ in 0..300 {
+        let side = if next() % 2 == 0 { Side::Buy } else { Side::Sell };
+        let price = 14950 + next() % 200;
+        match next() % 4 {
+            0 => {
+                let order = Order::limit("AAPL".to_string(), side, price, 1 + next() % 60)
+                    .with_display_quantity(10);
+                live.push(engine.submit_order(order).unwrap().order_id);
+            }
+            1 => {
+                live.push(engine.submit_order(Order::limit("AAPL".to_string(), side, price, 1 + next() % 60)).unwrap().order_id);
+            }
+            2 => {
+                engine.submit_order(Order::market("AAPL".to_string(), side, 1 + next() % 80)).unwrap();
+            }
+            _ => {
+                if !live.is_empty() {
+                    let id = live.swap_remove((next() as usize) % live.len());
+                    engine.cancel_order("AAPL", id);
+                }
+            }
+        }
+    }
+
+    apply(&mut replica, &engine.take_market_data("AAPL"));
+    assert_eq!(replica, snapshot(&engine, "AAPL"));
+}
+
+#[test]
+fn test_market_data_off_by_default() {
+    let mut engine = MatchingEngine::new();
+
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15000, 100)).unwrap();
+    assert!(engine.take_market_data("AAPL").is_empty());
+    assert_eq!(engine.get_order_book("AAPL").unwrap().sequence(), 0);
+}
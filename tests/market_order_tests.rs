@@ -0,0 +1,88 @@
+use rustex::{MarketOrderPolicy, MatchingEngine, Order, OrderError, Side, TimeInForce};
+
+#[test]
+fn test_market_remainder_cancelled_by_default() {
+    let mut engine = MatchingEngine::new();
+
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15000, 60)).unwrap();
+
+    let report = engine.submit_order(Order::market("AAPL".to_string(), Side::Buy, 100)).unwrap();
+    assert_eq!(report.filled_quantity, 60);
+    assert_eq!(report.cancelled_quantity, 40, "Unfilled market quantity should be reported");
+    assert_eq!(report.leaves_quantity, 0);
+    assert!(!report.rested);
+
+    if let Some(book) = engine.get_order_book("AAPL") {
+        assert!(book.best_bid().is_none());
+        assert!(book.best_ask().is_none());
+    }
+}
+
+#[test]
+fn test_market_order_into_empty_book_cancelled() {
+    let mut engine = MatchingEngine::new();
+
+    let report = engine.submit_order(Order::market("AAPL".to_string(), Side::Sell, 100)).unwrap();
+    assert!(report.trades.is_empty());
+    assert_eq!(report.cancelled_quantity, 100);
+}
+
+#[test]
+fn test_reject_market_order_without_liquidity() {
+    let mut engine = MatchingEngine::new()
+        .with_market_order_policy(MarketOrderPolicy::RejectIfNoLiquidity);
+
+    // Bids exist but no asks - a market buy has nothing to trade against
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 14900, 100)).unwrap();
+
+    match engine.submit_order(Order::market("AAPL".to_string(), Side::Buy, 100)) {
+        Err(e) => assert_eq!(e, OrderError::NoLiquidity),
+        Ok(_) => panic!("Should have rejected market order into empty side"),
+    }
+
+    // With liquidity, the remainder is cancelled as usual
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15000, 30)).unwrap();
+    let report = engine.submit_order(Order::market("AAPL".to_string(), Side::Buy, 100)).unwrap();
+    assert_eq!(report.filled_quantity, 30);
+    assert_eq!(report.cancelled_quantity, 70);
+}
+
+#[test]
+fn test_market_to_limit_rests_at_last_fill_price() {
+    let mut engine = MatchingEngine::new()
+        .with_market_order_policy(MarketOrderPolicy::ConvertToLimit);
+
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15000, 50)).unwrap();
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15100, 50)).unwrap();
+
+    let report = engine.submit_order(Order::market("AAPL".to_string(), Side::Buy, 150)).unwrap();
+    assert_eq!(report.filled_quantity, 100);
+    assert_eq!(report.leaves_quantity, 50);
+    assert_eq!(report.cancelled_quantity, 0);
+    assert!(report.rested);
+
+    // Remainder rests as a limit bid at the last fill price
+    if let Some(book) = engine.get_order_book("AAPL") {
+        assert_eq!(book.best_bid(), Some(15100));
+        assert!(book.best_ask().is_none());
+    }
+    assert!(engine.cancel_order("AAPL", report.order_id));
+}
+
+#[test]
+fn test_market_to_limit_without_fills_is_cancelled() {
+    let mut engine = MatchingEngine::new()
+        .with_market_order_policy(MarketOrderPolicy::ConvertToLimit);
+
+    let report = engine.submit_order(Order::market("AAPL".to_string(), Side::Buy, 100)).unwrap();
+    assert_eq!(report.cancelled_quantity, 100, "No fill price to convert at");
+    assert!(!report.rested);
+
+    // IOC market-to-limit never rests
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15000, 10)).unwrap();
+    let ioc = Order::market("AAPL".to_string(), Side::Buy, 100)
+        .with_time_in_force(TimeInForce::ImmediateOrCancel);
+    let report = engine.submit_order(ioc).unwrap();
+    assert_eq!(report.filled_quantity, 10);
+    assert_eq!(report.cancelled_quantity, 90);
+}
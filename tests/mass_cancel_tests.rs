@@ -0,0 +1,100 @@
+use rustex::{MassCancelFilter, MatchingEngine, Order, Side};
+
+fn quote(engine: &mut MatchingEngine, symbol: &str, side: Side, price: u64, owner: &str) -> u64 {
+    let order = Order::limit(symbol.to_string(), side, price, 100).with_owner(owner.to_string());
+    engine.submit_order(order).unwrap().order_id
+}
+
+#[test]
+fn test_mass_cancel_by_owner_across_symbols() {
+    let mut engine = MatchingEngine::new();
+
+    let a = quote(&mut engine, "AAPL", Side::Buy, 15000, "mm1");
+    let b = quote(&mut engine, "MSFT", Side::Sell, 30000, "mm1");
+    let other = quote(&mut engine, "AAPL", Side::Buy, 14990, "mm2");
+
+    let filter = MassCancelFilter { owner: Some("mm1".to_string()), ..Default::default() };
+    let mut cancelled = engine.mass_cancel(&filter);
+    cancelled.sort();
+    assert_eq!(cancelled, vec![a, b]);
+
+    let book = engine.get_order_book("AAPL").unwrap();
+    assert_eq!(book.best_bid(), Some(14990), "Other account's quote remains");
+    book.check_invariants().unwrap();
+    let book = engine.get_order_book("MSFT").unwrap();
+    assert!(book.best_ask().is_none());
+    assert!(engine.cancel_order("AAPL", other));
+}
+
+#[test]
+fn test_mass_cancel_by_symbol_and_side() {
+    let mut engine = MatchingEngine::new();
+
+    let bid = quote(&mut engine, "AAPL", Side::Buy, 15000, "mm1");
+    quote(&mut engine, "AAPL", Side::Sell, 15100, "mm1");
+    quote(&mut engine, "MSFT", Side::Buy, 30000, "mm1");
+
+    let filter = MassCancelFilter {
+        symbol: Some("AAPL".to_string()),
+        side: Some(Side::Buy),
+        ..Default::default()
+    };
+    assert_eq!(engine.mass_cancel(&filter), vec![bid]);
+
+    let book = engine.get_order_book("AAPL").unwrap();
+    assert!(book.best_bid().is_none());
+    assert_eq!(book.best_ask(), Some(15100));
+    let book = engine.get_order_book("MSFT").unwrap();
+    assert_eq!(book.best_bid(), Some(30000), "Other symbols untouched");
+}
+
+#[test]
+fn test_mass_cancel_price_band() {
+    let mut engine = MatchingEngine::new();
+
+    for price in [14900, 14950, 15000, 15050] {
+        quote(&mut engine, "AAPL", Side::Buy, price, "mm1");
+    }
+
+    let filter = MassCancelFilter { min_price: Some(14950), max_price: Some(15000), ..Default::default() };
+    assert_eq!(engine.mass_cancel(&filter).len(), 2, "Band is inclusive on both ends");
+
+    let book = engine.get_order_book("AAPL").unwrap();
+    assert_eq!(book.best_bid(), Some(15050));
+    book.check_invariants().unwrap();
+
+    let filter = MassCancelFilter { max_price: Some(14949), ..Default::default() };
+    assert_eq!(engine.mass_cancel(&filter).len(), 1);
+}
+
+#[test]
+fn test_mass_cancel_no_match() {
+    let mut engine = MatchingEngine::new();
+
+    quote(&mut engine, "AAPL", Side::Buy, 15000, "mm1");
+
+    let filter = MassCancelFilter { owner: Some("nobody".to_string()), ..Default::default() };
+    assert!(engine.mass_cancel(&filter).is_empty());
+    assert!(engine.mass_cancel(&MassCancelFilter { symbol: Some("TSLA".to_string()), ..Default::default() }).is_empty());
+
+    // Empty filter cancels everything
+    assert_eq!(engine.mass_cancel(&MassCancelFilter::default()).len(), 1);
+}
+
+#[test]
+fn test_mass_cancel_order_is_deterministic() {
+    let mut engine = MatchingEngine::new();
+
+    // Submitted in reverse symbol order, asks before bids
+    let mut expected = Vec::new();
+    for symbol in ["SYM9", "SYM5", "SYM3", "SYM1", "SYM0"] {
+        let ask = quote(&mut engine, symbol, Side::Sell, 200, "mm1");
+        let bid = quote(&mut engine, symbol, Side::Buy, 100, "mm1");
+        expected.push((symbol, vec![bid, ask]));
+    }
+    expected.sort();
+    let expected: Vec<u64> = expected.into_iter().flat_map(|(_, ids)| ids).collect();
+
+    let filter = MassCancelFilter { owner: Some("mm1".to_string()), ..Default::default() };
+    assert_eq!(engine.mass_cancel(&filter), expected, "Symbol order, then bids before asks");
+}
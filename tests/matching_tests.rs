@@ -1,4 +1,4 @@
-use rustex::{MatchingEngine, Order, Side};
+use rustex::{ExecutionReport, MatchingEngine, Order, Side};
 
 #[test]
 fn test_basic_buy_sell_match() {
@@ -7,14 +7,14 @@ fn test_basic_buy_sell_match() {
     // Add sell order first
     let sell_order = Order::limit("AAPL".to_string(), Side::Sell, 15000, 100);
     match engine.submit_order(sell_order) {
-        Ok(trades) => assert!(trades.is_empty(), "First order should not match"),
+        Ok(ExecutionReport { trades, .. }) => assert!(trades.is_empty(), "First order should not match"),
         Err(e) => panic!("Valid order rejected: {}", e),
     }
     
     // Add matching buy order
     let buy_order = Order::limit("AAPL".to_string(), Side::Buy, 15000, 100);
     match engine.submit_order(buy_order) {
-        Ok(trades) => {
+        Ok(ExecutionReport { trades, .. }) => {
             assert_eq!(trades.len(), 1, "Should create exactly one trade");
             assert_eq!(trades[0].quantity, 100, "Trade quantity should be 100");
             assert_eq!(trades[0].price, 15000, "Trade price should be 15000");
@@ -38,14 +38,14 @@ fn test_partial_fill_buyer() {
     // Add small sell order
     let sell_order = Order::limit("AAPL".to_string(), Side::Sell, 15000, 50);
     match engine.submit_order(sell_order) {
-        Ok(trades) => assert!(trades.is_empty()),
+        Ok(ExecutionReport { trades, .. }) => assert!(trades.is_empty()),
         Err(e) => panic!("Valid order rejected: {}", e),
     }
     
     // Add larger buy order (only 50 should match)
     let buy_order = Order::limit("AAPL".to_string(), Side::Buy, 15000, 150);
     match engine.submit_order(buy_order) {
-        Ok(trades) => {
+        Ok(ExecutionReport { trades, .. }) => {
             assert_eq!(trades.len(), 1, "Should create exactly one trade");
             assert_eq!(trades[0].quantity, 50, "Should only match available 50 shares");
         }
@@ -68,14 +68,14 @@ fn test_partial_fill_seller() {
     // Add large buy order
     let buy_order = Order::limit("AAPL".to_string(), Side::Buy, 15000, 200);
     match engine.submit_order(buy_order) {
-        Ok(trades) => assert!(trades.is_empty()),
+        Ok(ExecutionReport { trades, .. }) => assert!(trades.is_empty()),
         Err(e) => panic!("Valid order rejected: {}", e),
     }
     
     // Add smaller sell order
     let sell_order = Order::limit("AAPL".to_string(), Side::Sell, 15000, 75);
     match engine.submit_order(sell_order) {
-        Ok(trades) => {
+        Ok(ExecutionReport { trades, .. }) => {
             assert_eq!(trades.len(), 1, "Should create exactly one trade");
             assert_eq!(trades[0].quantity, 75, "Should match full sell order");
         }
@@ -120,7 +120,7 @@ fn test_price_time_priority_fifo() {
     // Add sell order that matches first two
     let sell_order = Order::limit("AAPL".to_string(), Side::Sell, 15000, 60);
     match engine.submit_order(sell_order) {
-        Ok(trades) => {
+        Ok(ExecutionReport { trades, .. }) => {
             assert_eq!(trades.len(), 2, "Should match with first two buy orders");
             // First trade should be with buy1 (30 shares)
             assert_eq!(trades[0].quantity, 30, "First trade should be 30 shares");
@@ -139,14 +139,14 @@ fn test_no_match_when_prices_dont_cross() {
     // Add buy at 14900
     let buy_order = Order::limit("AAPL".to_string(), Side::Buy, 14900, 100);
     match engine.submit_order(buy_order) {
-        Ok(trades) => assert!(trades.is_empty()),
+        Ok(ExecutionReport { trades, .. }) => assert!(trades.is_empty()),
         Err(e) => panic!("Valid order rejected: {}", e),
     }
     
     // Add sell at 15100 (spread of 200)
     let sell_order = Order::limit("AAPL".to_string(), Side::Sell, 15100, 100);
     match engine.submit_order(sell_order) {
-        Ok(trades) => {
+        Ok(ExecutionReport { trades, .. }) => {
             assert!(trades.is_empty(), "Orders should not match - prices don't cross");
         }
         Err(e) => panic!("Valid order rejected: {}", e),
@@ -177,7 +177,7 @@ fn test_aggressive_order_matches_multiple_levels() {
     // Aggressive buy sweeps first two levels
     let buy_order = Order::limit("AAPL".to_string(), Side::Buy, 15150, 120);
     match engine.submit_order(buy_order) {
-        Ok(trades) => {
+        Ok(ExecutionReport { trades, .. }) => {
             assert_eq!(trades.len(), 2, "Should match across two price levels");
             assert_eq!(trades[0].price, 15000, "First match at best ask");
             assert_eq!(trades[0].quantity, 50, "Match full first level");
@@ -208,7 +208,7 @@ fn test_market_order_sweeps_book() {
     // Market buy should match all available
     let market_buy = Order::market("AAPL".to_string(), Side::Buy, 100);
     match engine.submit_order(market_buy) {
-        Ok(trades) => {
+        Ok(ExecutionReport { trades, .. }) => {
             assert_eq!(trades.len(), 2, "Should match both levels");
             assert_eq!(trades[0].quantity, 50, "Match first level");
             assert_eq!(trades[1].quantity, 50, "Match part of second level");
@@ -228,7 +228,7 @@ fn test_multi_symbol_isolation() {
     // Add MSFT sell order at same price
     let msft_sell = Order::limit("MSFT".to_string(), Side::Sell, 15000, 100);
     match engine.submit_order(msft_sell) {
-        Ok(trades) => {
+        Ok(ExecutionReport { trades, .. }) => {
             assert!(trades.is_empty(), "Different symbols should not match");
         }
         Err(e) => panic!("Valid order rejected: {}", e),
@@ -0,0 +1,131 @@
+use rustex::{MatchingEngine, Order, OrderError, SelfTradePrevention, Side, TimeInForce};
+
+#[test]
+fn test_min_quantity_not_met_does_not_trade() {
+    let mut engine = MatchingEngine::new();
+
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15000, 30)).unwrap();
+
+    let buy = Order::limit("AAPL".to_string(), Side::Buy, 15000, 100).with_min_quantity(50);
+    let report = engine.submit_order(buy).unwrap();
+    assert!(report.trades.is_empty(), "Only 30 available, minimum is 50");
+    assert!(report.rested, "Unmatched order rests like any limit order");
+
+    let ioc = Order::limit("AAPL".to_string(), Side::Buy, 15000, 100)
+        .with_min_quantity(50)
+        .with_time_in_force(TimeInForce::ImmediateOrCancel);
+    let report = engine.submit_order(ioc).unwrap();
+    assert!(report.trades.is_empty());
+    assert_eq!(report.cancelled_quantity, 100);
+
+    let book = engine.get_order_book("AAPL").unwrap();
+    assert_eq!(book.best_ask(), Some(15000), "Resting sell should be untouched");
+}
+
+#[test]
+fn test_min_quantity_met_across_levels() {
+    let mut engine = MatchingEngine::new();
+
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15000, 30)).unwrap();
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15100, 30)).unwrap();
+
+    let buy = Order::limit("AAPL".to_string(), Side::Buy, 15100, 100).with_min_quantity(50);
+    let report = engine.submit_order(buy).unwrap();
+    assert_eq!(report.filled_quantity, 60);
+    assert_eq!(report.leaves_quantity, 40);
+    assert!(report.rested);
+}
+
+#[test]
+fn test_resting_aon_skipped_keeps_queue_position() {
+    let mut engine = MatchingEngine::new();
+
+    let aon = engine.submit_order(
+        Order::limit("AAPL".to_string(), Side::Buy, 15000, 100).with_all_or_none(true),
+    ).unwrap();
+    let normal = engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15000, 50)).unwrap();
+
+    // Too small for the AON order, so the order behind it fills
+    let trades = engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15000, 30)).unwrap().trades;
+    assert_eq!(trades.len(), 1);
+    assert_eq!(trades[0].buyer_order_id, normal.order_id);
+
+    // Large enough: the AON order is still first in line
+    let trades = engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15000, 110)).unwrap().trades;
+    assert_eq!(trades[0].buyer_order_id, aon.order_id);
+    assert_eq!(trades[0].quantity, 100, "AON fills completely in one trade");
+    assert_eq!(trades[1].buyer_order_id, normal.order_id);
+    assert_eq!(trades[1].quantity, 10);
+
+    if let Some(book) = engine.get_order_book("AAPL") {
+        book.check_invariants().unwrap();
+    }
+}
+
+#[test]
+fn test_incoming_aon_rests_until_fully_fillable() {
+    let mut engine = MatchingEngine::new();
+
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15000, 40)).unwrap();
+
+    let aon = engine.submit_order(
+        Order::limit("AAPL".to_string(), Side::Buy, 15000, 100).with_all_or_none(true),
+    ).unwrap();
+    assert!(aon.trades.is_empty(), "Only 40 of 100 available");
+    assert!(aon.rested);
+
+    let trades = engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15000, 100)).unwrap().trades;
+    assert_eq!(trades.len(), 1);
+    assert_eq!(trades[0].buyer_order_id, aon.order_id);
+    assert_eq!(trades[0].quantity, 100);
+}
+
+#[test]
+fn test_fok_ignores_unfillable_aon_liquidity() {
+    let mut engine = MatchingEngine::new();
+
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15000, 100).with_all_or_none(true)).unwrap();
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15000, 20)).unwrap();
+
+    // 120 rests, but a 50 lot can only reach the 20 lot
+    let fok = Order::limit("AAPL".to_string(), Side::Sell, 15000, 50)
+        .with_time_in_force(TimeInForce::FillOrKill);
+    let report = engine.submit_order(fok).unwrap();
+    assert!(report.trades.is_empty(), "FOK must not partially fill");
+    assert_eq!(report.cancelled_quantity, 50);
+}
+
+#[test]
+fn test_reject_invalid_min_quantity_and_aon_iceberg() {
+    let mut engine = MatchingEngine::new();
+
+    let order = Order::limit("AAPL".to_string(), Side::Buy, 15000, 100).with_min_quantity(0);
+    assert_eq!(engine.submit_order(order).unwrap_err(), OrderError::InvalidMinQuantity);
+
+    let order = Order::limit("AAPL".to_string(), Side::Buy, 15000, 100).with_min_quantity(101);
+    assert_eq!(engine.submit_order(order).unwrap_err(), OrderError::InvalidMinQuantity);
+
+    let order = Order::limit("AAPL".to_string(), Side::Buy, 15000, 100)
+        .with_all_or_none(true)
+        .with_display_quantity(10);
+    assert_eq!(engine.submit_order(order).unwrap_err(), OrderError::InvalidDisplayQuantity);
+}
+
+#[test]
+fn test_min_quantity_counts_self_trade_prevention() {
+    let mut engine = MatchingEngine::new();
+
+    let owned = |side, price, quantity, owner: &str| {
+        Order::limit("AAPL".to_string(), side, price, quantity).with_owner(owner.to_string())
+    };
+    engine.submit_order(owned(Side::Sell, 100, 50, "MM2")).unwrap();
+    engine.submit_order(owned(Side::Sell, 100, 50, "MM1")).unwrap();
+    engine.submit_order(owned(Side::Sell, 101, 50, "MM2")).unwrap();
+
+    // Only the first 50 could trade before hitting our own order
+    let buy = owned(Side::Buy, 101, 150, "MM1")
+        .with_min_quantity(100)
+        .with_self_trade_prevention(SelfTradePrevention::CancelNewest);
+    let report = engine.submit_order(buy).unwrap();
+    assert!(report.trades.is_empty(), "Would trade 50, below the minimum of 100");
+}
@@ -0,0 +1,110 @@
+use rustex::{MatchingEngine, Order, OrderError, Side};
+
+#[test]
+fn test_reduce_quantity_keeps_priority() {
+    let mut engine = MatchingEngine::new();
+
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15000, 100)).unwrap(); // ID 1
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15000, 100)).unwrap(); // ID 2
+
+    // Shrink order #1 at the same price
+    let trades = engine.modify_order("AAPL", 1, 15000, 40).unwrap().trades;
+    assert!(trades.is_empty());
+
+    // Order #1 should still be first in line, with the reduced size
+    let sell = Order::limit("AAPL".to_string(), Side::Sell, 15000, 60);
+    let trades = engine.submit_order(sell).unwrap().trades;
+    assert_eq!(trades.len(), 2);
+    assert_eq!(trades[0].buyer_order_id, 1, "Reduced order keeps its place");
+    assert_eq!(trades[0].quantity, 40, "Reduced quantity should be used");
+    assert_eq!(trades[1].buyer_order_id, 2);
+    assert_eq!(trades[1].quantity, 20);
+}
+
+#[test]
+fn test_increase_quantity_loses_priority() {
+    let mut engine = MatchingEngine::new();
+
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15000, 50)).unwrap(); // ID 1
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15000, 50)).unwrap(); // ID 2
+
+    let trades = engine.modify_order("AAPL", 1, 15000, 80).unwrap().trades;
+    assert!(trades.is_empty());
+
+    // Order #2 is now ahead of the amended order
+    let sell = Order::limit("AAPL".to_string(), Side::Sell, 15000, 60);
+    let trades = engine.submit_order(sell).unwrap().trades;
+    assert_eq!(trades[0].buyer_order_id, 2, "Increased order goes to the back");
+    assert_eq!(trades[0].quantity, 50);
+    assert_eq!(trades[1].buyer_order_id, 1, "Amended order keeps its ID");
+    assert_eq!(trades[1].quantity, 10);
+}
+
+#[test]
+fn test_price_change_that_crosses_matches() {
+    let mut engine = MatchingEngine::new();
+
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15100, 30)).unwrap(); // ID 1
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15000, 100)).unwrap(); // ID 2
+
+    // Raise the bid through the ask
+    let trades = engine.modify_order("AAPL", 2, 15100, 100).unwrap().trades;
+    assert_eq!(trades.len(), 1, "New price should cross the ask");
+    assert_eq!(trades[0].buyer_order_id, 2);
+    assert_eq!(trades[0].seller_order_id, 1);
+    assert_eq!(trades[0].price, 15100);
+    assert_eq!(trades[0].quantity, 30);
+
+    // Remainder rests at the new price
+    if let Some(book) = engine.get_order_book("AAPL") {
+        assert_eq!(book.best_bid(), Some(15100));
+        assert!(book.best_ask().is_none());
+    }
+    assert!(engine.cancel_order("AAPL", 2), "Amended order is still cancellable by its ID");
+}
+
+#[test]
+fn test_modify_unknown_order() {
+    let mut engine = MatchingEngine::new();
+
+    match engine.modify_order("AAPL", 42, 15000, 100) {
+        Err(e) => assert_eq!(e, OrderError::OrderNotFound(42)),
+        Ok(_) => panic!("Should not amend a non-existent order"),
+    }
+
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15000, 100)).unwrap();
+    match engine.modify_order("MSFT", 1, 15000, 50) {
+        Err(e) => assert_eq!(e, OrderError::OrderNotFound(1)),
+        Ok(_) => panic!("Should not find order in wrong symbol"),
+    }
+}
+
+#[test]
+fn test_modify_rejects_zero_quantity_and_price() {
+    let mut engine = MatchingEngine::new();
+
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15000, 100)).unwrap();
+
+    assert_eq!(engine.modify_order("AAPL", 1, 15000, 0), Err(OrderError::ZeroQuantity));
+    assert_eq!(engine.modify_order("AAPL", 1, 0, 100), Err(OrderError::ZeroPrice));
+
+    // Original order untouched
+    if let Some(book) = engine.get_order_book("AAPL") {
+        assert_eq!(book.best_bid(), Some(15000));
+    }
+}
+
+#[test]
+fn test_modify_by_id_without_symbol() {
+    let mut engine = MatchingEngine::new();
+
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15100, 30)).unwrap(); // ID 1
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15000, 100)).unwrap(); // ID 2
+
+    let report = engine.modify_order_by_id(2, 15100, 100).unwrap();
+    assert_eq!(report.filled_quantity, 30);
+    assert_eq!(report.leaves_quantity, 70);
+
+    assert_eq!(engine.modify_order_by_id(1, 15100, 10), Err(OrderError::OrderNotFound(1)), "Filled order is gone");
+    assert_eq!(engine.modify_order_by_id(42, 15000, 10), Err(OrderError::OrderNotFound(42)));
+}
@@ -0,0 +1,234 @@
+use rustex::{MatchingEngine, Order, OrderError, OrderState, Side};
+
+/// Resting buy at 15000 and a trade at 15000 so stops have a last price
+fn setup_market(engine: &mut MatchingEngine) {
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15000, 10)).unwrap();
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15000, 10)).unwrap();
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 14800, 500)).unwrap();
+}
+
+#[test]
+fn test_oco_fill_cancels_other_leg() {
+    let mut engine = MatchingEngine::new();
+    setup_market(&mut engine);
+
+    let (take_profit, stop_loss) = engine.submit_oco(
+        Order::limit("AAPL".to_string(), Side::Sell, 15100, 50),
+        Order::stop("AAPL".to_string(), Side::Sell, 14900, 50),
+    ).unwrap();
+    assert!(take_profit.rested && stop_loss.rested);
+
+    // Partial fill on the limit leg is enough to cancel the stop
+    let report = engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15100, 20)).unwrap();
+    assert_eq!(report.trades[0].seller_order_id, take_profit.order_id);
+    assert_eq!(report.cancelled_orders, vec![stop_loss.order_id]);
+
+    let triggers = engine.get_trigger_book("AAPL").unwrap();
+    assert!(triggers.is_empty(), "Stop leg should be cancelled");
+    assert!(engine.cancel_order("AAPL", take_profit.order_id), "Filled leg keeps working");
+}
+
+#[test]
+fn test_oco_triggered_stop_cancels_limit_leg() {
+    let mut engine = MatchingEngine::new();
+    setup_market(&mut engine);
+
+    let (take_profit, stop_loss) = engine.submit_oco(
+        Order::limit("AAPL".to_string(), Side::Sell, 15100, 50),
+        Order::stop("AAPL".to_string(), Side::Sell, 14900, 50),
+    ).unwrap();
+
+    // Trade at 14800 fires the stop, which sells into the 14800 bid
+    let report = engine.submit_order(Order::market("AAPL".to_string(), Side::Sell, 10)).unwrap();
+    assert_eq!(report.triggered.len(), 1);
+    assert_eq!(report.triggered[0].order_id, stop_loss.order_id);
+    assert!(report.cancelled_orders.contains(&take_profit.order_id));
+
+    let book = engine.get_order_book("AAPL").unwrap();
+    assert!(book.get_order(take_profit.order_id).is_none());
+}
+
+#[test]
+fn test_oco_cancel_one_leg_cancels_both() {
+    let mut engine = MatchingEngine::new();
+    setup_market(&mut engine);
+
+    let (take_profit, stop_loss) = engine.submit_oco(
+        Order::limit("AAPL".to_string(), Side::Sell, 15100, 50),
+        Order::stop("AAPL".to_string(), Side::Sell, 14900, 50),
+    ).unwrap();
+
+    assert!(engine.cancel_order("AAPL", stop_loss.order_id));
+    assert!(!engine.cancel_order("AAPL", take_profit.order_id), "Other leg already cancelled");
+}
+
+#[test]
+fn test_oco_first_leg_fills_on_arrival() {
+    let mut engine = MatchingEngine::new();
+    setup_market(&mut engine);
+
+    let (first, second) = engine.submit_oco(
+        Order::limit("AAPL".to_string(), Side::Sell, 14800, 50),
+        Order::limit("AAPL".to_string(), Side::Sell, 15200, 50),
+    ).unwrap();
+    assert_eq!(first.filled_quantity, 50);
+    assert_eq!(second.cancelled_quantity, 50, "Second leg never goes live");
+    assert!(!second.rested);
+
+    let book = engine.get_order_book("AAPL").unwrap();
+    assert!(book.best_ask().is_none());
+}
+
+#[test]
+fn test_reject_invalid_oco() {
+    let mut engine = MatchingEngine::new();
+
+    let result = engine.submit_oco(
+        Order::limit("AAPL".to_string(), Side::Sell, 15100, 50),
+        Order::market("AAPL".to_string(), Side::Sell, 50),
+    );
+    assert_eq!(result.unwrap_err(), OrderError::InvalidOrderGroup);
+
+    let result = engine.submit_oco(
+        Order::limit("AAPL".to_string(), Side::Sell, 15100, 50),
+        Order::limit("MSFT".to_string(), Side::Sell, 30000, 50),
+    );
+    assert_eq!(result.unwrap_err(), OrderError::InvalidOrderGroup);
+
+    // Legs on opposite sides that cross would trade with each other
+    let result = engine.submit_oco(
+        Order::limit("AAPL".to_string(), Side::Buy, 100, 10),
+        Order::limit("AAPL".to_string(), Side::Sell, 100, 10),
+    );
+    assert_eq!(result.unwrap_err(), OrderError::InvalidOrderGroup);
+    assert!(engine.get_order_book("AAPL").is_none(), "Nothing was submitted");
+
+    // Non-crossing opposite-side legs are fine
+    let (buy, sell) = engine.submit_oco(
+        Order::limit("AAPL".to_string(), Side::Buy, 99, 10),
+        Order::limit("AAPL".to_string(), Side::Sell, 101, 10),
+    ).unwrap();
+    assert!(buy.rested && sell.rested);
+}
+
+#[test]
+fn test_bracket_exits_activate_after_entry_fills() {
+    let mut engine = MatchingEngine::new();
+    setup_market(&mut engine);
+
+    let entry = engine.submit_bracket(
+        Order::limit("AAPL".to_string(), Side::Buy, 14950, 100),
+        15200,
+        14700,
+    ).unwrap();
+    assert!(entry.rested);
+    assert!(entry.triggered.is_empty(), "Exits wait for the entry");
+
+    let report = engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 14950, 100)).unwrap();
+    assert_eq!(report.triggered.len(), 2, "Entry fill activates both exits");
+    let (take_profit, stop_loss) = (&report.triggered[0], &report.triggered[1]);
+    assert_eq!(take_profit.leaves_quantity, 100);
+    assert_eq!(stop_loss.leaves_quantity, 100);
+
+    let book = engine.get_order_book("AAPL").unwrap();
+    assert_eq!(book.best_ask(), Some(15200), "Take-profit rests on the book");
+    let triggers = engine.get_trigger_book("AAPL").unwrap();
+    assert_eq!(triggers.len(), 1, "Stop-loss waits for its trigger");
+
+    // Exits are an OCO pair
+    assert!(engine.cancel_order("AAPL", take_profit.order_id));
+    assert!(!engine.cancel_order("AAPL", stop_loss.order_id));
+}
+
+#[test]
+fn test_bracket_entry_cancel() {
+    let mut engine = MatchingEngine::new();
+    setup_market(&mut engine);
+
+    // Unfilled entry: exits are discarded
+    let entry = engine.submit_bracket(Order::limit("AAPL".to_string(), Side::Buy, 14950, 100), 15200, 14700).unwrap();
+    assert!(engine.cancel_order("AAPL", entry.order_id));
+    let book = engine.get_order_book("AAPL").unwrap();
+    assert!(book.best_ask().is_none());
+
+    // Partially filled entry: exits cover the filled quantity
+    let entry = engine.submit_bracket(Order::limit("AAPL".to_string(), Side::Buy, 14950, 100), 15200, 14700).unwrap();
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 14950, 30)).unwrap();
+    assert!(engine.cancel_order("AAPL", entry.order_id));
+
+    let book = engine.get_order_book("AAPL").unwrap();
+    assert_eq!(book.best_ask(), Some(15200));
+    book.check_invariants().unwrap();
+    let triggers = engine.get_trigger_book("AAPL").unwrap();
+    assert_eq!(triggers.len(), 1);
+}
+
+#[test]
+fn test_bracket_exits_follow_partial_fills() {
+    let mut engine = MatchingEngine::new();
+    setup_market(&mut engine);
+
+    let entry = engine.submit_bracket(Order::limit("AAPL".to_string(), Side::Buy, 14950, 100), 15200, 14700).unwrap();
+
+    // Filled quantity is protected right away, while the rest keeps working
+    let report = engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 14950, 40)).unwrap();
+    assert_eq!(report.triggered.len(), 2);
+    assert_eq!(report.triggered[0].leaves_quantity, 40);
+    assert_eq!(report.triggered[1].leaves_quantity, 40);
+    assert!(engine.get_order(entry.order_id).is_some(), "Entry still working");
+
+    let report = engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 14950, 60)).unwrap();
+    assert_eq!(report.triggered.len(), 2, "Next fill gets its own exit pair");
+    assert_eq!(report.triggered[0].leaves_quantity, 60);
+
+    let book = engine.get_order_book("AAPL").unwrap();
+    assert_eq!(book.depth(1).asks[0].quantity, 100, "Take-profits cover the whole fill");
+    let triggers = engine.get_trigger_book("AAPL").unwrap();
+    assert_eq!(triggers.len(), 2);
+}
+
+#[test]
+fn test_bracket_exits_never_trade_with_entry() {
+    let mut engine = MatchingEngine::new();
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 95, 5)).unwrap();
+
+    // Take-profit below the entry's limit would cross its resting remainder
+    let result = engine.submit_bracket(Order::limit("AAPL".to_string(), Side::Buy, 100, 10), 98, 90);
+    assert_eq!(result.unwrap_err(), OrderError::InvalidOrderGroup);
+
+    // Partial fill at a better price than the limit; the exit rests clear of the entry
+    let entry = engine.submit_bracket(Order::limit("AAPL".to_string(), Side::Buy, 100, 10), 105, 90).unwrap();
+    assert_eq!(entry.filled_quantity, 5);
+    assert_eq!(entry.triggered.len(), 2);
+    assert!(entry.triggered[0].trades.is_empty(), "Take-profit must not hit its own entry");
+
+    // Even an entry amended through its take-profit skips it
+    let report = engine.modify_order("AAPL", entry.order_id, 106, 5).unwrap();
+    assert!(report.trades.is_empty());
+
+    let status = engine.order_status(entry.order_id).unwrap();
+    assert_eq!(status.state, OrderState::PartiallyFilled);
+    assert_eq!(status.filled_quantity, 5);
+}
+
+#[test]
+fn test_oco_triggered_stop_skips_its_sibling() {
+    let mut engine = MatchingEngine::new();
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 100, 1)).unwrap();
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 100, 1)).unwrap();
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 120, 10)).unwrap();
+
+    let (take_profit, stop) = engine.submit_oco(
+        Order::limit("AAPL".to_string(), Side::Sell, 110, 10),
+        Order::stop("AAPL".to_string(), Side::Buy, 105, 10),
+    ).unwrap();
+
+    // Print at 105 fires the buy stop, which must not lift its own sell leg at 110
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 105, 1)).unwrap();
+    let report = engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 105, 1)).unwrap();
+    assert_eq!(report.triggered.len(), 1);
+    assert_eq!(report.triggered[0].order_id, stop.order_id);
+    assert!(report.triggered[0].trades.iter().all(|t| t.seller_order_id != take_profit.order_id));
+    assert_eq!(report.triggered[0].trades[0].price, 120);
+    assert_eq!(engine.order_status(take_profit.order_id).unwrap().state, OrderState::Cancelled);
+}
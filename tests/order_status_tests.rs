@@ -0,0 +1,143 @@
+use rustex::{MatchingEngine, Order, OrderState, SelfTradePrevention, Side, TimeInForce};
+
+#[test]
+fn test_status_of_resting_order() {
+    let mut engine = MatchingEngine::new();
+
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15000, 100)).unwrap();
+    let order = engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15000, 50)).unwrap();
+
+    let status = engine.order_status(order.order_id).unwrap();
+    assert_eq!(status.state, OrderState::New);
+    assert_eq!(status.symbol, "AAPL");
+    assert_eq!(status.side, Side::Buy);
+    assert_eq!(status.original_quantity, 50);
+    assert_eq!(status.remaining_quantity, 50);
+    assert_eq!(status.filled_quantity, 0);
+    assert_eq!(status.average_fill_price, None);
+    assert_eq!(status.price, Some(15000));
+    assert_eq!(status.queue_position, Some(1), "One order ahead at the level");
+}
+
+#[test]
+fn test_status_partial_fill_and_average_price() {
+    let mut engine = MatchingEngine::new();
+
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15000, 30)).unwrap();
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15100, 10)).unwrap();
+
+    let buy = engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15100, 100)).unwrap();
+    let status = engine.order_status(buy.order_id).unwrap();
+    assert_eq!(status.state, OrderState::PartiallyFilled);
+    assert_eq!(status.filled_quantity, 40);
+    assert_eq!(status.remaining_quantity, 60);
+    assert_eq!(status.average_fill_price, Some(15025.0));
+    assert_eq!(status.queue_position, Some(0));
+
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15100, 60)).unwrap();
+    let status = engine.order_status(buy.order_id).unwrap();
+    assert_eq!(status.state, OrderState::Filled);
+    assert_eq!(status.remaining_quantity, 0);
+    assert_eq!(status.price, None, "No longer on the book");
+    assert_eq!(status.queue_position, None);
+}
+
+#[test]
+fn test_status_terminal_states() {
+    let mut engine = MatchingEngine::new();
+
+    let cancelled = engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15000, 100)).unwrap();
+    engine.cancel_order("AAPL", cancelled.order_id);
+    assert_eq!(engine.order_status(cancelled.order_id).unwrap().state, OrderState::Cancelled);
+
+    let day = engine.submit_order(
+        Order::limit("AAPL".to_string(), Side::Buy, 15000, 100).with_time_in_force(TimeInForce::Day),
+    ).unwrap();
+    engine.expire_orders(u64::MAX);
+    assert_eq!(engine.order_status(day.order_id).unwrap().state, OrderState::Expired);
+
+    // IOC remainder is cancelled after a partial fill
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15000, 20)).unwrap();
+    let ioc = engine.submit_order(
+        Order::limit("AAPL".to_string(), Side::Buy, 15000, 50).with_time_in_force(TimeInForce::ImmediateOrCancel),
+    ).unwrap();
+    let status = engine.order_status(ioc.order_id).unwrap();
+    assert_eq!(status.state, OrderState::Cancelled);
+    assert_eq!(status.filled_quantity, 20);
+
+    assert!(engine.order_status(999).is_none(), "Unknown order");
+}
+
+#[test]
+fn test_status_after_amend_down_then_fill() {
+    let mut engine = MatchingEngine::new();
+
+    let order = engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15000, 100)).unwrap();
+    engine.modify_order("AAPL", order.order_id, 15000, 40).unwrap();
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15000, 40)).unwrap();
+
+    let status = engine.order_status(order.order_id).unwrap();
+    assert_eq!(status.state, OrderState::Filled, "Amended quantity fully traded");
+    assert_eq!(status.original_quantity, 100);
+    assert_eq!(status.filled_quantity, 40);
+}
+
+#[test]
+fn test_status_of_pending_stop() {
+    let mut engine = MatchingEngine::new();
+
+    let stop = engine.submit_order(Order::stop("AAPL".to_string(), Side::Sell, 14000, 100)).unwrap();
+    let status = engine.order_status(stop.order_id).unwrap();
+    assert_eq!(status.state, OrderState::New);
+    assert_eq!(status.remaining_quantity, 100);
+    assert_eq!(status.price, None, "Waiting off the book");
+}
+
+#[test]
+fn test_status_after_self_trade_decrement() {
+    let mut engine = MatchingEngine::new();
+    let owned = |side, price, quantity, owner: &str| {
+        Order::limit("AAPL".to_string(), side, price, quantity)
+            .with_owner(owner.to_string())
+            .with_self_trade_prevention(SelfTradePrevention::DecrementAndCancel)
+    };
+
+    // Resting order decremented by 30, then its remaining 70 fill
+    let resting = engine.submit_order(owned(Side::Sell, 15000, 100, "MM1")).unwrap();
+    engine.submit_order(owned(Side::Buy, 15000, 30, "MM1")).unwrap();
+    engine.submit_order(owned(Side::Buy, 15000, 70, "MM2")).unwrap();
+
+    let status = engine.order_status(resting.order_id).unwrap();
+    assert_eq!(status.state, OrderState::Filled, "Nothing was left to cancel");
+    assert_eq!(status.original_quantity, 100);
+    assert_eq!(status.filled_quantity, 70);
+
+    // Incoming order decremented by 30, fills 50 and rests 20, which fill later
+    engine.submit_order(owned(Side::Sell, 15000, 30, "MM1")).unwrap();
+    engine.submit_order(owned(Side::Sell, 15000, 50, "MM2")).unwrap();
+    let incoming = engine.submit_order(owned(Side::Buy, 15000, 100, "MM1")).unwrap();
+    assert_eq!(incoming.leaves_quantity, 20);
+    engine.submit_order(owned(Side::Sell, 15000, 20, "MM3")).unwrap();
+
+    let status = engine.order_status(incoming.order_id).unwrap();
+    assert_eq!(status.state, OrderState::Filled);
+    assert_eq!(status.filled_quantity, 70);
+}
+
+#[test]
+fn test_status_retention_drops_oldest_finished() {
+    let mut engine = MatchingEngine::new().with_status_retention(2);
+
+    let resting = engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 14000, 10)).unwrap();
+    let ioc = |engine: &mut MatchingEngine| {
+        let order = Order::limit("AAPL".to_string(), Side::Buy, 15000, 10)
+            .with_time_in_force(TimeInForce::ImmediateOrCancel);
+        engine.submit_order(order).unwrap().order_id
+    };
+    let finished: Vec<u64> = (0..3).map(|_| ioc(&mut engine)).collect();
+
+    assert!(engine.order_status(finished[0]).is_none(), "Oldest finished order is forgotten");
+    assert_eq!(engine.order_status(finished[1]).unwrap().state, OrderState::Cancelled);
+    assert_eq!(engine.order_status(finished[2]).unwrap().state, OrderState::Cancelled);
+    assert_eq!(engine.order_status(resting.order_id).unwrap().state, OrderState::New, "Live orders are always kept");
+}
@@ -0,0 +1,134 @@
+use rustex::{MatchingEngine, Order, OrderError, PegType, Side};
+
+fn setup_book(engine: &mut MatchingEngine, bid: u64, ask: u64) {
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, bid, 100)).unwrap();
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, ask, 100)).unwrap();
+}
+
+fn resting_price(engine: &MatchingEngine, order_id: u64) -> Option<u64> {
+    engine.get_order_book("AAPL")?.get_order(order_id)?.price
+}
+
+#[test]
+fn test_midpoint_peg_rounds_passively() {
+    let mut engine = MatchingEngine::new();
+    setup_book(&mut engine, 15000, 15011);
+
+    let buy = engine.submit_order(Order::pegged("AAPL".to_string(), Side::Buy, PegType::Midpoint, 10)).unwrap();
+    let sell = engine.submit_order(Order::pegged("AAPL".to_string(), Side::Sell, PegType::Midpoint, 10)).unwrap();
+    assert!(buy.rested && sell.rested, "Odd spread leaves the two midpoint pegs apart");
+    assert_eq!(resting_price(&engine, buy.order_id), Some(15005), "Buy rounds down");
+    assert_eq!(resting_price(&engine, sell.order_id), Some(15006), "Sell rounds up");
+
+    if let Some(book) = engine.get_order_book("AAPL") {
+        book.check_invariants().unwrap();
+    }
+}
+
+#[test]
+fn test_midpoint_pegs_cross_at_midpoint() {
+    let mut engine = MatchingEngine::new();
+    setup_book(&mut engine, 15000, 15010);
+
+    let buy = engine.submit_order(Order::pegged("AAPL".to_string(), Side::Buy, PegType::Midpoint, 30)).unwrap();
+    let report = engine.submit_order(Order::pegged("AAPL".to_string(), Side::Sell, PegType::Midpoint, 20)).unwrap();
+
+    assert_eq!(report.trades.len(), 1);
+    assert_eq!(report.trades[0].buyer_order_id, buy.order_id);
+    assert_eq!(report.trades[0].price, 15005);
+    assert_eq!(report.trades[0].quantity, 20);
+}
+
+#[test]
+fn test_primary_peg_follows_bid_on_add_and_cancel() {
+    let mut engine = MatchingEngine::new();
+    setup_book(&mut engine, 15000, 15010);
+
+    let peg = engine.submit_order(Order::pegged("AAPL".to_string(), Side::Buy, PegType::Primary, 50)).unwrap();
+    assert_eq!(resting_price(&engine, peg.order_id), Some(15000));
+
+    // A better bid pulls the peg up
+    let better = engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15002, 10)).unwrap();
+    assert_eq!(better.repriced.len(), 1, "Add should reprice the peg");
+    assert_eq!(resting_price(&engine, peg.order_id), Some(15002));
+
+    // Cancelling it drops the peg back
+    assert!(engine.cancel_order("AAPL", better.order_id));
+    assert_eq!(resting_price(&engine, peg.order_id), Some(15000));
+}
+
+#[test]
+fn test_peg_reprices_after_match() {
+    let mut engine = MatchingEngine::new();
+    setup_book(&mut engine, 15000, 15010);
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15020, 100)).unwrap();
+
+    let peg = engine.submit_order(
+        Order::pegged("AAPL".to_string(), Side::Sell, PegType::Primary, 50).with_peg_offset(1),
+    ).unwrap();
+    assert_eq!(resting_price(&engine, peg.order_id), Some(15011));
+
+    // Sweep the best ask; the peg moves behind the next level
+    let report = engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15010, 100)).unwrap();
+    assert_eq!(report.filled_quantity, 100);
+    assert_eq!(report.repriced.len(), 1);
+    assert_eq!(resting_price(&engine, peg.order_id), Some(15021));
+}
+
+#[test]
+fn test_market_peg_with_offset_and_limit() {
+    let mut engine = MatchingEngine::new();
+    setup_book(&mut engine, 15000, 15010);
+
+    // One tick inside the ask, capped at 15005
+    let peg = engine.submit_order(
+        Order::pegged("AAPL".to_string(), Side::Buy, PegType::Market, 10)
+            .with_peg_offset(-1)
+            .with_peg_limit(15005),
+    ).unwrap();
+    assert!(peg.trades.is_empty());
+    assert_eq!(resting_price(&engine, peg.order_id), Some(15005), "Limit caps the pegged price");
+
+    // Amending a peg changes its cap
+    engine.modify_order("AAPL", peg.order_id, 15020, 10).unwrap();
+    assert_eq!(resting_price(&engine, peg.order_id), Some(15009));
+}
+
+#[test]
+fn test_market_peg_sweeps_many_levels() {
+    let mut engine = MatchingEngine::new();
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 1000, 100)).unwrap();
+    for level in 0..5000 {
+        engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 2000 + level, 1)).unwrap();
+    }
+
+    // Each level taken moves the ask the peg follows; every fill belongs to this report
+    let report = engine.submit_order(
+        Order::pegged("AAPL".to_string(), Side::Buy, PegType::Market, 50_000),
+    ).unwrap();
+    assert_eq!(report.trades.len(), 5000);
+    assert_eq!(report.filled_quantity, 5000);
+    assert_eq!(report.leaves_quantity, 45_000);
+    assert!(report.repriced.is_empty(), "The peg's own reprices are merged, not nested");
+
+    let status = engine.order_status(report.order_id).unwrap();
+    assert_eq!(status.filled_quantity, 5000);
+    assert_eq!(resting_price(&engine, report.order_id), Some(6999), "No ask left to follow");
+    engine.get_order_book("AAPL").unwrap().check_invariants().unwrap();
+}
+
+#[test]
+fn test_reject_peg_without_reference() {
+    let mut engine = MatchingEngine::new();
+
+    let order = Order::pegged("AAPL".to_string(), Side::Buy, PegType::Primary, 10);
+    assert_eq!(engine.submit_order(order).unwrap_err(), OrderError::NoReferencePrice);
+
+    // Midpoint needs both sides
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15000, 100)).unwrap();
+    let order = Order::pegged("AAPL".to_string(), Side::Buy, PegType::Midpoint, 10);
+    assert_eq!(engine.submit_order(order).unwrap_err(), OrderError::NoReferencePrice);
+
+    let order = Order::pegged("AAPL".to_string(), Side::Buy, PegType::Primary, 10).with_peg_limit(0);
+    assert_eq!(engine.submit_order(order).unwrap_err(), OrderError::ZeroPrice);
+}
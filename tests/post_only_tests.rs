@@ -0,0 +1,84 @@
+use rustex::{MatchingEngine, Order, OrderError, PostOnly, Side, TimeInForce};
+
+#[test]
+fn test_post_only_rests_when_not_crossing() {
+    let mut engine = MatchingEngine::new();
+
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15100, 100)).unwrap();
+
+    let buy = Order::limit("AAPL".to_string(), Side::Buy, 15000, 100).with_post_only(PostOnly::Reject);
+    let report = engine.submit_order(buy).unwrap();
+    assert!(report.trades.is_empty());
+    assert!(report.rested);
+
+    if let Some(book) = engine.get_order_book("AAPL") {
+        assert_eq!(book.best_bid(), Some(15000));
+    }
+}
+
+#[test]
+fn test_post_only_reject_when_crossing() {
+    let mut engine = MatchingEngine::new();
+
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15000, 100)).unwrap();
+
+    let sell = Order::limit("AAPL".to_string(), Side::Sell, 15000, 50).with_post_only(PostOnly::Reject);
+    assert_eq!(engine.submit_order(sell).unwrap_err(), OrderError::WouldTakeLiquidity);
+
+    // Resting bid untouched
+    let trades = engine.submit_order(Order::market("AAPL".to_string(), Side::Sell, 100)).unwrap().trades;
+    assert_eq!(trades[0].quantity, 100);
+}
+
+#[test]
+fn test_post_only_slide_reprices_passive() {
+    let mut engine = MatchingEngine::new();
+
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15100, 100)).unwrap();
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 14900, 100)).unwrap();
+
+    let buy = Order::limit("AAPL".to_string(), Side::Buy, 15200, 40).with_post_only(PostOnly::Slide);
+    let report = engine.submit_order(buy).unwrap();
+    assert!(report.trades.is_empty(), "Post-only must never take liquidity");
+    assert!(report.rested);
+
+    let sell = Order::limit("AAPL".to_string(), Side::Sell, 14000, 40).with_post_only(PostOnly::Slide);
+    let report = engine.submit_order(sell).unwrap();
+    assert!(report.trades.is_empty());
+
+    if let Some(book) = engine.get_order_book("AAPL") {
+        assert_eq!(book.best_bid(), Some(15099), "Buy slides one tick below the ask");
+        assert_eq!(book.best_ask(), Some(15100), "Sell slides one tick above the new bid");
+    }
+}
+
+#[test]
+fn test_post_only_amend_cannot_cross() {
+    let mut engine = MatchingEngine::new();
+
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15100, 100)).unwrap();
+    let maker = engine.submit_order(
+        Order::limit("AAPL".to_string(), Side::Buy, 15000, 100).with_post_only(PostOnly::Reject),
+    ).unwrap();
+
+    assert_eq!(
+        engine.modify_order("AAPL", maker.order_id, 15100, 100),
+        Err(OrderError::WouldTakeLiquidity)
+    );
+    if let Some(book) = engine.get_order_book("AAPL") {
+        assert_eq!(book.best_bid(), Some(15000), "Rejected amend leaves the order as it was");
+    }
+}
+
+#[test]
+fn test_reject_post_only_on_non_resting_orders() {
+    let mut engine = MatchingEngine::new();
+
+    let market = Order::market("AAPL".to_string(), Side::Buy, 10).with_post_only(PostOnly::Reject);
+    assert_eq!(engine.submit_order(market).unwrap_err(), OrderError::InvalidPostOnly);
+
+    let ioc = Order::limit("AAPL".to_string(), Side::Buy, 15000, 10)
+        .with_post_only(PostOnly::Slide)
+        .with_time_in_force(TimeInForce::ImmediateOrCancel);
+    assert_eq!(engine.submit_order(ioc).unwrap_err(), OrderError::InvalidPostOnly);
+}
@@ -0,0 +1,78 @@
+use rustex::{MatchingEngine, Order, Side, TimeInForce};
+
+#[test]
+fn test_report_returns_assigned_id_for_cancel() {
+    let mut engine = MatchingEngine::new();
+
+    let first = engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15000, 100)).unwrap();
+    let second = engine.submit_order(Order::limit("MSFT".to_string(), Side::Sell, 30000, 100)).unwrap();
+    assert_ne!(first.order_id, second.order_id, "Each order gets a unique ID");
+    assert!(first.timestamp > 0, "Timestamp should be assigned");
+
+    // Use the reported ID to cancel
+    assert!(engine.cancel_order("MSFT", second.order_id));
+    assert!(engine.cancel_order("AAPL", first.order_id));
+}
+
+#[test]
+fn test_report_resting_order() {
+    let mut engine = MatchingEngine::new();
+
+    let report = engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15000, 100)).unwrap();
+    assert_eq!(report.filled_quantity, 0);
+    assert_eq!(report.leaves_quantity, 100);
+    assert!(report.rested);
+    assert!(report.trades.is_empty());
+}
+
+#[test]
+fn test_report_partial_fill() {
+    let mut engine = MatchingEngine::new();
+
+    let maker = engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15000, 30)).unwrap();
+
+    let report = engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15000, 100)).unwrap();
+    assert_eq!(report.filled_quantity, 30);
+    assert_eq!(report.leaves_quantity, 70);
+    assert!(report.rested, "Remainder should rest on the book");
+    assert_eq!(report.trades.len(), 1);
+    assert_eq!(report.trades[0].buyer_order_id, report.order_id);
+    assert_eq!(report.trades[0].seller_order_id, maker.order_id);
+}
+
+#[test]
+fn test_report_full_fill_and_ioc_remainder() {
+    let mut engine = MatchingEngine::new();
+
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15000, 60)).unwrap();
+
+    let report = engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15000, 40)).unwrap();
+    assert_eq!(report.filled_quantity, 40);
+    assert_eq!(report.leaves_quantity, 0);
+    assert!(!report.rested, "Fully filled order does not rest");
+
+    let ioc = Order::limit("AAPL".to_string(), Side::Buy, 15000, 50)
+        .with_time_in_force(TimeInForce::ImmediateOrCancel);
+    let report = engine.submit_order(ioc).unwrap();
+    assert_eq!(report.filled_quantity, 20);
+    assert_eq!(report.leaves_quantity, 0, "IOC remainder is not left open");
+    assert!(!report.rested);
+}
+
+#[test]
+fn test_amend_report_keeps_order_id() {
+    let mut engine = MatchingEngine::new();
+
+    let original = engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15000, 100)).unwrap();
+
+    let reduced = engine.modify_order("AAPL", original.order_id, 15000, 60).unwrap();
+    assert_eq!(reduced.order_id, original.order_id);
+    assert_eq!(reduced.timestamp, original.timestamp, "In-place reduction keeps priority");
+    assert_eq!(reduced.leaves_quantity, 60);
+    assert!(reduced.rested);
+
+    let repriced = engine.modify_order("AAPL", original.order_id, 15100, 60).unwrap();
+    assert_eq!(repriced.order_id, original.order_id);
+    assert!(repriced.timestamp >= original.timestamp);
+    assert_eq!(repriced.leaves_quantity, 60);
+}
@@ -0,0 +1,166 @@
+use rustex::{MatchingEngine, Order, SelfTradePrevention, Side, TimeInForce};
+
+fn owned(side: Side, price: u64, qty: u64, owner: &str) -> Order {
+    Order::limit("AAPL".to_string(), side, price, qty).with_owner(owner.to_string())
+}
+
+#[test]
+fn test_different_owners_trade_normally() {
+    let mut engine = MatchingEngine::new();
+
+    engine.submit_order(owned(Side::Sell, 15000, 100, "MM1")).unwrap();
+
+    let buy = owned(Side::Buy, 15000, 100, "MM2")
+        .with_self_trade_prevention(SelfTradePrevention::CancelNewest);
+    let report = engine.submit_order(buy).unwrap();
+    assert_eq!(report.filled_quantity, 100);
+    assert!(report.cancelled_orders.is_empty());
+}
+
+#[test]
+fn test_self_trade_allowed_without_mode() {
+    let mut engine = MatchingEngine::new();
+
+    engine.submit_order(owned(Side::Sell, 15000, 100, "MM1")).unwrap();
+    let report = engine.submit_order(owned(Side::Buy, 15000, 100, "MM1")).unwrap();
+    assert_eq!(report.filled_quantity, 100, "No STP mode means no prevention");
+}
+
+#[test]
+fn test_cancel_newest() {
+    let mut engine = MatchingEngine::new();
+
+    let resting = engine.submit_order(owned(Side::Sell, 15000, 100, "MM1")).unwrap();
+
+    let buy = owned(Side::Buy, 15000, 60, "MM1")
+        .with_self_trade_prevention(SelfTradePrevention::CancelNewest);
+    let report = engine.submit_order(buy).unwrap();
+    assert!(report.trades.is_empty(), "Must not trade against own order");
+    assert_eq!(report.cancelled_quantity, 60);
+    assert!(!report.rested, "Incoming order is cancelled, not rested");
+
+    // Resting order untouched
+    if let Some(book) = engine.get_order_book("AAPL") {
+        assert_eq!(book.best_ask(), Some(15000));
+        assert!(book.best_bid().is_none());
+    }
+    assert!(engine.cancel_order("AAPL", resting.order_id));
+}
+
+#[test]
+fn test_cancel_oldest_keeps_matching() {
+    let mut engine = MatchingEngine::new();
+
+    let own = engine.submit_order(owned(Side::Sell, 15000, 50, "MM1")).unwrap();
+    let other = engine.submit_order(owned(Side::Sell, 15000, 50, "MM2")).unwrap();
+
+    let buy = owned(Side::Buy, 15000, 80, "MM1")
+        .with_self_trade_prevention(SelfTradePrevention::CancelOldest);
+    let report = engine.submit_order(buy).unwrap();
+
+    assert_eq!(report.cancelled_orders, vec![own.order_id]);
+    assert_eq!(report.trades.len(), 1);
+    assert_eq!(report.trades[0].seller_order_id, other.order_id);
+    assert_eq!(report.filled_quantity, 50);
+    assert_eq!(report.leaves_quantity, 30, "Remainder rests as usual");
+    assert!(!engine.cancel_order("AAPL", own.order_id), "Own resting order was cancelled");
+}
+
+#[test]
+fn test_cancel_both() {
+    let mut engine = MatchingEngine::new();
+
+    let other = engine.submit_order(owned(Side::Sell, 15000, 20, "MM2")).unwrap();
+    let own = engine.submit_order(owned(Side::Sell, 15000, 50, "MM1")).unwrap();
+
+    let buy = owned(Side::Buy, 15000, 100, "MM1")
+        .with_self_trade_prevention(SelfTradePrevention::CancelBoth);
+    let report = engine.submit_order(buy).unwrap();
+
+    // Fills against the other account first, then hits its own order
+    assert_eq!(report.trades.len(), 1);
+    assert_eq!(report.trades[0].seller_order_id, other.order_id);
+    assert_eq!(report.cancelled_orders, vec![own.order_id]);
+    assert_eq!(report.cancelled_quantity, 80);
+    assert!(!report.rested);
+
+    if let Some(book) = engine.get_order_book("AAPL") {
+        assert!(book.best_ask().is_none());
+        assert!(book.best_bid().is_none());
+        book.check_invariants().unwrap();
+    }
+}
+
+#[test]
+fn test_decrement_and_cancel_resting_larger() {
+    let mut engine = MatchingEngine::new();
+
+    let resting = engine.submit_order(owned(Side::Sell, 15000, 100, "MM1")).unwrap();
+
+    let buy = owned(Side::Buy, 15000, 40, "MM1")
+        .with_self_trade_prevention(SelfTradePrevention::DecrementAndCancel);
+    let report = engine.submit_order(buy).unwrap();
+    assert!(report.trades.is_empty());
+    assert_eq!(report.cancelled_quantity, 40);
+    assert!(report.cancelled_orders.is_empty(), "Resting order is only decremented");
+
+    // Resting order shrank to 60
+    let report = engine.submit_order(owned(Side::Buy, 15000, 100, "MM2")).unwrap();
+    assert_eq!(report.trades[0].seller_order_id, resting.order_id);
+    assert_eq!(report.filled_quantity, 60);
+}
+
+#[test]
+fn test_decrement_and_cancel_incoming_larger() {
+    let mut engine = MatchingEngine::new();
+
+    let own = engine.submit_order(owned(Side::Sell, 15000, 30, "MM1")).unwrap();
+    engine.submit_order(owned(Side::Sell, 15100, 50, "MM2")).unwrap();
+
+    let buy = owned(Side::Buy, 15100, 100, "MM1")
+        .with_self_trade_prevention(SelfTradePrevention::DecrementAndCancel);
+    let report = engine.submit_order(buy).unwrap();
+    assert_eq!(report.cancelled_orders, vec![own.order_id]);
+    assert_eq!(report.cancelled_quantity, 30);
+    assert_eq!(report.filled_quantity, 50, "Decremented remainder keeps matching");
+    assert_eq!(report.leaves_quantity, 20);
+}
+
+#[test]
+fn test_fok_never_partially_fills_through_self_trade() {
+    for mode in [
+        SelfTradePrevention::CancelNewest,
+        SelfTradePrevention::CancelBoth,
+        SelfTradePrevention::DecrementAndCancel,
+    ] {
+        let mut engine = MatchingEngine::new();
+
+        engine.submit_order(owned(Side::Sell, 100, 50, "MM2")).unwrap();
+        engine.submit_order(owned(Side::Sell, 100, 50, "MM1")).unwrap();
+        engine.submit_order(owned(Side::Sell, 101, 50, "MM2")).unwrap();
+
+        let buy = owned(Side::Buy, 101, 100, "MM1")
+            .with_time_in_force(TimeInForce::FillOrKill)
+            .with_self_trade_prevention(mode);
+        let report = engine.submit_order(buy).unwrap();
+        assert!(report.trades.is_empty(), "{mode:?}: FOK must fill completely or not at all");
+        assert_eq!(report.cancelled_quantity, 100);
+        assert!(report.cancelled_orders.is_empty(), "{mode:?}: book untouched");
+    }
+}
+
+#[test]
+fn test_fok_fills_past_own_order_with_cancel_oldest() {
+    let mut engine = MatchingEngine::new();
+
+    engine.submit_order(owned(Side::Sell, 100, 50, "MM2")).unwrap();
+    let own = engine.submit_order(owned(Side::Sell, 100, 50, "MM1")).unwrap();
+    engine.submit_order(owned(Side::Sell, 101, 50, "MM2")).unwrap();
+
+    let buy = owned(Side::Buy, 101, 100, "MM1")
+        .with_time_in_force(TimeInForce::FillOrKill)
+        .with_self_trade_prevention(SelfTradePrevention::CancelOldest);
+    let report = engine.submit_order(buy).unwrap();
+    assert_eq!(report.filled_quantity, 100);
+    assert_eq!(report.cancelled_orders, vec![own.order_id]);
+}
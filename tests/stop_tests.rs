@@ -0,0 +1,151 @@
+use rustex::{MatchingEngine, Order, OrderError, Side, TimeInForce};
+
+#[test]
+fn test_stop_is_held_off_book() {
+    let mut engine = MatchingEngine::new();
+
+    let stop = engine.submit_order(Order::stop("AAPL".to_string(), Side::Sell, 14900, 50)).unwrap();
+    assert!(stop.rested);
+    assert_eq!(stop.leaves_quantity, 50);
+    assert!(stop.trades.is_empty());
+
+    assert!(engine.get_order_book("AAPL").is_none_or(|book| book.best_ask().is_none()));
+    assert_eq!(engine.get_trigger_book("AAPL").map(|t| t.len()), Some(1));
+}
+
+#[test]
+fn test_sell_stop_triggers_on_falling_price() {
+    let mut engine = MatchingEngine::new();
+
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15000, 10)).unwrap();
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 14900, 10)).unwrap();
+    let support = engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 14800, 100)).unwrap();
+    let stop = engine.submit_order(Order::stop("AAPL".to_string(), Side::Sell, 14900, 50)).unwrap();
+
+    // Trade at 15000 does not reach the trigger
+    let report = engine.submit_order(Order::market("AAPL".to_string(), Side::Sell, 10)).unwrap();
+    assert!(report.triggered.is_empty());
+    assert_eq!(engine.last_trade_price("AAPL"), Some(15000));
+
+    // Trade at 14900 fires the stop as a market sell
+    let report = engine.submit_order(Order::market("AAPL".to_string(), Side::Sell, 10)).unwrap();
+    assert_eq!(report.triggered.len(), 1);
+    let fired = &report.triggered[0];
+    assert_eq!(fired.order_id, stop.order_id);
+    assert_eq!(fired.filled_quantity, 50);
+    assert_eq!(fired.trades[0].buyer_order_id, support.order_id);
+    assert_eq!(fired.trades[0].price, 14800);
+    assert_eq!(engine.get_trigger_book("AAPL").map(|t| t.len()), Some(0));
+}
+
+#[test]
+fn test_buy_stop_limit_rests_after_trigger() {
+    let mut engine = MatchingEngine::new();
+
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15100, 10)).unwrap();
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15300, 100)).unwrap();
+    let stop = engine.submit_order(
+        Order::stop_limit("AAPL".to_string(), Side::Buy, 15100, 15200, 40),
+    ).unwrap();
+
+    let report = engine.submit_order(Order::market("AAPL".to_string(), Side::Buy, 10)).unwrap();
+    assert_eq!(report.triggered.len(), 1);
+    let fired = &report.triggered[0];
+    assert!(fired.trades.is_empty(), "Limit 15200 does not reach the 15300 ask");
+    assert!(fired.rested);
+
+    if let Some(book) = engine.get_order_book("AAPL") {
+        assert_eq!(book.best_bid(), Some(15200), "Triggered stop-limit rests at its limit");
+    }
+    assert!(engine.cancel_order("AAPL", stop.order_id));
+}
+
+#[test]
+fn test_stop_cascade_within_one_submit() {
+    let mut engine = MatchingEngine::new();
+
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15000, 10)).unwrap();
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 14900, 10)).unwrap();
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 14800, 10)).unwrap();
+
+    // First stop trades at 14900, which reaches the second stop
+    let first = engine.submit_order(Order::stop("AAPL".to_string(), Side::Sell, 15000, 10)).unwrap();
+    let second = engine.submit_order(Order::stop("AAPL".to_string(), Side::Sell, 14900, 10)).unwrap();
+
+    let report = engine.submit_order(Order::market("AAPL".to_string(), Side::Sell, 10)).unwrap();
+    let fired: Vec<u64> = report.triggered.iter().map(|r| r.order_id).collect();
+    assert_eq!(fired, vec![first.order_id, second.order_id]);
+    assert_eq!(report.triggered[1].trades[0].price, 14800);
+    assert_eq!(engine.last_trade_price("AAPL"), Some(14800));
+
+    if let Some(book) = engine.get_order_book("AAPL") {
+        assert!(book.best_bid().is_none());
+    }
+}
+
+#[test]
+fn test_stop_fires_on_print_inside_sweep() {
+    let mut engine = MatchingEngine::new();
+
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 101, 1)).unwrap();
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 101, 1)).unwrap();
+    let support = engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 95, 5)).unwrap();
+    let stop = engine.submit_order(Order::stop("AAPL".to_string(), Side::Sell, 100, 5)).unwrap();
+
+    // Prints at 99 then 103: the last price never reaches the trigger, the first does
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 99, 1)).unwrap();
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 103, 1)).unwrap();
+    let report = engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 103, 2)).unwrap();
+    assert_eq!(report.trades.iter().map(|t| t.price).collect::<Vec<_>>(), vec![99, 103]);
+
+    assert_eq!(report.triggered.len(), 1);
+    assert_eq!(report.triggered[0].order_id, stop.order_id);
+    assert_eq!(report.triggered[0].trades[0].buyer_order_id, support.order_id);
+    assert_eq!(engine.get_trigger_book("AAPL").map(|t| t.len()), Some(0));
+}
+
+#[test]
+fn test_stop_already_through_trigger_fires_immediately() {
+    let mut engine = MatchingEngine::new();
+
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15000, 100)).unwrap();
+    engine.submit_order(Order::market("AAPL".to_string(), Side::Buy, 10)).unwrap();
+
+    // Last trade 15000 is already above a 14900 buy stop
+    let report = engine.submit_order(Order::stop("AAPL".to_string(), Side::Buy, 14900, 20)).unwrap();
+    assert_eq!(report.triggered.len(), 1);
+    assert_eq!(report.triggered[0].order_id, report.order_id);
+    assert_eq!(report.triggered[0].filled_quantity, 20);
+}
+
+#[test]
+fn test_cancel_and_expire_pending_stops() {
+    let mut engine = MatchingEngine::new();
+
+    let stop = engine.submit_order(Order::stop("AAPL".to_string(), Side::Sell, 14900, 50)).unwrap();
+    assert!(engine.cancel_order("AAPL", stop.order_id));
+    assert!(!engine.cancel_order("AAPL", stop.order_id));
+
+    let day = Order::stop("AAPL".to_string(), Side::Sell, 14900, 50)
+        .with_time_in_force(TimeInForce::Day);
+    let day = engine.submit_order(day).unwrap();
+    let expired = engine.expire_orders(u64::MAX);
+    assert_eq!(expired.len(), 1);
+    assert_eq!(expired[0].id, day.order_id);
+}
+
+#[test]
+fn test_reject_stop_without_trigger() {
+    let mut engine = MatchingEngine::new();
+
+    let mut stop = Order::stop("AAPL".to_string(), Side::Sell, 14900, 50);
+    stop.trigger_price = None;
+    assert_eq!(engine.submit_order(stop).unwrap_err(), OrderError::MissingTriggerPrice);
+
+    let zero = Order::stop_limit("AAPL".to_string(), Side::Buy, 0, 15000, 50);
+    assert_eq!(engine.submit_order(zero).unwrap_err(), OrderError::MissingTriggerPrice);
+
+    let mut no_limit = Order::stop_limit("AAPL".to_string(), Side::Buy, 15000, 15000, 50);
+    no_limit.price = None;
+    assert_eq!(engine.submit_order(no_limit).unwrap_err(), OrderError::MissingLimitPrice);
+}
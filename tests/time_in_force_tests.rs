@@ -0,0 +1,199 @@
+use rustex::{MatchingEngine, Order, OrderError, Side, TimeInForce};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const NANOS_PER_DAY: u64 = 24 * 60 * 60 * 1_000_000_000;
+
+fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos() as u64
+}
+
+#[test]
+fn test_ioc_partial_fill_does_not_rest() {
+    let mut engine = MatchingEngine::new();
+
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15000, 50)).unwrap();
+
+    // IOC buy for more than is available
+    let ioc = Order::limit("AAPL".to_string(), Side::Buy, 15000, 120)
+        .with_time_in_force(TimeInForce::ImmediateOrCancel);
+    let trades = engine.submit_order(ioc).unwrap().trades;
+    assert_eq!(trades.len(), 1);
+    assert_eq!(trades[0].quantity, 50, "IOC should take all available liquidity");
+
+    // Remainder must not rest on the book
+    if let Some(book) = engine.get_order_book("AAPL") {
+        assert!(book.best_bid().is_none(), "IOC remainder should be cancelled");
+        assert!(book.best_ask().is_none());
+    }
+}
+
+#[test]
+fn test_ioc_without_liquidity_does_not_rest() {
+    let mut engine = MatchingEngine::new();
+
+    let ioc = Order::limit("AAPL".to_string(), Side::Buy, 15000, 100)
+        .with_time_in_force(TimeInForce::ImmediateOrCancel);
+    let trades = engine.submit_order(ioc).unwrap().trades;
+    assert!(trades.is_empty());
+
+    if let Some(book) = engine.get_order_book("AAPL") {
+        assert!(book.best_bid().is_none(), "IOC should never rest");
+    }
+}
+
+#[test]
+fn test_fok_fills_completely_across_levels() {
+    let mut engine = MatchingEngine::new();
+
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15000, 50)).unwrap();
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15100, 50)).unwrap();
+
+    let fok = Order::limit("AAPL".to_string(), Side::Buy, 15100, 100)
+        .with_time_in_force(TimeInForce::FillOrKill);
+    let trades = engine.submit_order(fok).unwrap().trades;
+    assert_eq!(trades.len(), 2, "FOK should sweep both levels");
+    assert_eq!(trades.iter().map(|t| t.quantity).sum::<u64>(), 100);
+
+    if let Some(book) = engine.get_order_book("AAPL") {
+        assert!(book.best_ask().is_none());
+        assert!(book.best_bid().is_none());
+    }
+}
+
+#[test]
+fn test_fok_kills_when_liquidity_insufficient() {
+    let mut engine = MatchingEngine::new();
+
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15000, 50)).unwrap();
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15200, 100)).unwrap();
+
+    // Only 50 available at or below 15100
+    let fok = Order::limit("AAPL".to_string(), Side::Buy, 15100, 100)
+        .with_time_in_force(TimeInForce::FillOrKill);
+    let trades = engine.submit_order(fok).unwrap().trades;
+    assert!(trades.is_empty(), "FOK must not partially fill");
+
+    // Book untouched
+    if let Some(book) = engine.get_order_book("AAPL") {
+        assert_eq!(book.best_ask(), Some(15000), "Resting sell should be untouched");
+        assert!(book.best_bid().is_none(), "FOK should never rest");
+    }
+
+    // Resting order still fully available
+    let trades = engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15000, 50)).unwrap().trades;
+    assert_eq!(trades.len(), 1);
+    assert_eq!(trades[0].quantity, 50);
+}
+
+#[test]
+fn test_fok_market_sell() {
+    let mut engine = MatchingEngine::new();
+
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15000, 40)).unwrap();
+
+    let fok = Order::market("AAPL".to_string(), Side::Sell, 50)
+        .with_time_in_force(TimeInForce::FillOrKill);
+    let trades = engine.submit_order(fok).unwrap().trades;
+    assert!(trades.is_empty(), "Not enough bids to fill 50");
+
+    let fok = Order::market("AAPL".to_string(), Side::Sell, 40)
+        .with_time_in_force(TimeInForce::FillOrKill);
+    let trades = engine.submit_order(fok).unwrap().trades;
+    assert_eq!(trades.len(), 1);
+    assert_eq!(trades[0].quantity, 40);
+}
+
+#[test]
+fn test_gtd_order_expires_at_expiry_time() {
+    let mut engine = MatchingEngine::new();
+    let expiry = now() + 60 * 1_000_000_000;
+
+    let gtd = Order::limit("AAPL".to_string(), Side::Buy, 15000, 100)
+        .with_time_in_force(TimeInForce::GoodTillDate(expiry));
+    engine.submit_order(gtd).unwrap();
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 14900, 100)).unwrap();
+
+    // Not yet expired
+    assert!(engine.expire_orders(expiry - 1).is_empty());
+
+    let expired = engine.expire_orders(expiry);
+    assert_eq!(expired.len(), 1, "Only the GTD order should expire");
+    assert_eq!(expired[0].id, 1);
+    assert_eq!(expired[0].quantity, 100);
+
+    // GTC order is left alone
+    if let Some(book) = engine.get_order_book("AAPL") {
+        assert_eq!(book.best_bid(), Some(14900));
+    }
+    assert!(!engine.cancel_order("AAPL", 1), "Expired order should no longer be cancellable");
+}
+
+#[test]
+fn test_day_orders_expire_at_end_of_day() {
+    let mut engine = MatchingEngine::new();
+
+    let day_buy = Order::limit("AAPL".to_string(), Side::Buy, 15000, 100)
+        .with_time_in_force(TimeInForce::Day);
+    let day_sell = Order::limit("MSFT".to_string(), Side::Sell, 30000, 100)
+        .with_time_in_force(TimeInForce::Day);
+    engine.submit_order(day_buy).unwrap();
+    engine.submit_order(day_sell).unwrap();
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15100, 100)).unwrap();
+
+    // Sweep at the start of the next day removes DAY orders from every book
+    let next_day = (now() / NANOS_PER_DAY + 1) * NANOS_PER_DAY;
+    let mut expired: Vec<u64> = engine.expire_orders(next_day).iter().map(|o| o.id).collect();
+    expired.sort();
+    assert_eq!(expired, vec![1, 2]);
+
+    if let Some(book) = engine.get_order_book("AAPL") {
+        assert!(book.best_bid().is_none(), "DAY bid should be gone");
+        assert_eq!(book.best_ask(), Some(15100), "GTC ask should remain");
+    }
+    if let Some(book) = engine.get_order_book("MSFT") {
+        assert!(book.best_ask().is_none(), "Empty price level should be removed");
+    }
+}
+
+#[test]
+fn test_expiry_sweep_is_in_symbol_order() {
+    let mut engine = MatchingEngine::new();
+
+    let mut expected = Vec::new();
+    for symbol in ["SYM9", "SYM5", "SYM3", "SYM1", "SYM0"] {
+        let order = Order::limit(symbol.to_string(), Side::Buy, 15000, 100)
+            .with_time_in_force(TimeInForce::Day);
+        expected.push((symbol, engine.submit_order(order).unwrap().order_id));
+    }
+    expected.sort();
+
+    let next_day = (now() / NANOS_PER_DAY + 1) * NANOS_PER_DAY;
+    let expired: Vec<u64> = engine.expire_orders(next_day).iter().map(|o| o.id).collect();
+    assert_eq!(expired, expected.into_iter().map(|(_, id)| id).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_reject_gtd_in_the_past() {
+    let mut engine = MatchingEngine::new();
+
+    let gtd = Order::limit("AAPL".to_string(), Side::Buy, 15000, 100)
+        .with_time_in_force(TimeInForce::GoodTillDate(1));
+    match engine.submit_order(gtd) {
+        Err(e) => assert_eq!(e, OrderError::ExpiryInPast),
+        Ok(_) => panic!("Should have rejected expired GTD order"),
+    }
+}
+
+#[test]
+fn test_fok_kill_reports_cancelled_quantity() {
+    let mut engine = MatchingEngine::new();
+
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15000, 50)).unwrap();
+
+    let fok = Order::limit("AAPL".to_string(), Side::Buy, 15000, 80)
+        .with_time_in_force(TimeInForce::FillOrKill);
+    let report = engine.submit_order(fok).unwrap();
+    assert_eq!(report.filled_quantity, 0);
+    assert_eq!(report.cancelled_quantity, 80, "Killed FOK cancels its full quantity");
+    assert!(!report.rested);
+}
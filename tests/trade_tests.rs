@@ -0,0 +1,57 @@
+use rustex::{MatchingEngine, Order, Side};
+
+#[test]
+fn test_trade_ids_are_unique_and_increasing() {
+    let mut engine = MatchingEngine::new();
+
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15000, 10)).unwrap();
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15100, 10)).unwrap();
+    engine.submit_order(Order::limit("MSFT".to_string(), Side::Sell, 30000, 10)).unwrap();
+
+    let first = engine.submit_order(Order::market("AAPL".to_string(), Side::Buy, 20)).unwrap().trades;
+    let second = engine.submit_order(Order::market("MSFT".to_string(), Side::Buy, 10)).unwrap().trades;
+
+    let ids: Vec<u64> = first.iter().chain(second.iter()).map(|t| t.trade_id).collect();
+    assert_eq!(ids.len(), 3);
+    assert!(ids.windows(2).all(|w| w[0] < w[1]), "Trade IDs should increase across symbols: {:?}", ids);
+}
+
+#[test]
+fn test_trade_carries_symbol() {
+    let mut engine = MatchingEngine::new();
+
+    engine.submit_order(Order::limit("MSFT".to_string(), Side::Buy, 30000, 10)).unwrap();
+    let trades = engine.submit_order(Order::limit("MSFT".to_string(), Side::Sell, 30000, 10)).unwrap().trades;
+
+    assert_eq!(trades[0].symbol, "MSFT");
+}
+
+#[test]
+fn test_buy_aggressor_maker_taker() {
+    let mut engine = MatchingEngine::new();
+
+    let maker = engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15000, 50)).unwrap();
+    let taker = engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15000, 50)).unwrap();
+
+    let trade = &taker.trades[0];
+    assert_eq!(trade.aggressor_side, Side::Buy);
+    assert_eq!(trade.taker_order_id, taker.order_id);
+    assert_eq!(trade.maker_order_id, maker.order_id);
+    assert_eq!(trade.buyer_order_id, taker.order_id);
+    assert_eq!(trade.seller_order_id, maker.order_id);
+}
+
+#[test]
+fn test_sell_aggressor_maker_taker() {
+    let mut engine = MatchingEngine::new();
+
+    let maker = engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15000, 50)).unwrap();
+    let taker = engine.submit_order(Order::market("AAPL".to_string(), Side::Sell, 50)).unwrap();
+
+    let trade = &taker.trades[0];
+    assert_eq!(trade.aggressor_side, Side::Sell);
+    assert_eq!(trade.taker_order_id, taker.order_id);
+    assert_eq!(trade.maker_order_id, maker.order_id);
+    assert_eq!(trade.buyer_order_id, maker.order_id);
+    assert_eq!(trade.seller_order_id, taker.order_id);
+}
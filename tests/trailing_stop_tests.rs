@@ -0,0 +1,107 @@
+use rustex::{MatchingEngine, Order, OrderError, Side, TrailingOffset};
+
+/// Print a one-lot trade at `price` (assumes nothing else crosses it)
+fn trade_at(engine: &mut MatchingEngine, price: u64) {
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, price, 1)).unwrap();
+    let report = engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, price, 1)).unwrap();
+    assert_eq!(report.trades.len(), 1, "Helper trade at {} should print", price);
+}
+
+fn trigger_of(engine: &MatchingEngine, order_id: u64) -> Option<u64> {
+    engine.get_trigger_book("AAPL")?.get_order(order_id)?.trigger_price
+}
+
+#[test]
+fn test_sell_trailing_stop_ratchets_up_and_fires_on_reversal() {
+    let mut engine = MatchingEngine::new();
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 10000, 100)).unwrap();
+
+    trade_at(&mut engine, 15000);
+    let stop = engine.submit_order(
+        Order::trailing_stop("AAPL".to_string(), Side::Sell, TrailingOffset::Absolute(100), 20),
+    ).unwrap();
+    assert_eq!(trigger_of(&engine, stop.order_id), Some(14900));
+
+    // Rally drags the trigger up
+    trade_at(&mut engine, 15200);
+    assert_eq!(trigger_of(&engine, stop.order_id), Some(15100));
+
+    // Dip that stays above the trigger doesn't loosen it
+    trade_at(&mut engine, 15150);
+    assert_eq!(trigger_of(&engine, stop.order_id), Some(15100));
+
+    // Reversal by the full offset fires the stop
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 15100, 1)).unwrap();
+    let report = engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 15100, 1)).unwrap();
+    assert_eq!(report.triggered.len(), 1);
+    assert_eq!(report.triggered[0].order_id, stop.order_id);
+    assert_eq!(report.triggered[0].filled_quantity, 20);
+    assert_eq!(report.triggered[0].trades[0].price, 10000);
+}
+
+#[test]
+fn test_trailing_stop_follows_prints_inside_sweep() {
+    let mut engine = MatchingEngine::new();
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 50, 100)).unwrap();
+
+    trade_at(&mut engine, 100);
+    let stop = engine.submit_order(
+        Order::trailing_stop("AAPL".to_string(), Side::Sell, TrailingOffset::Absolute(5), 20),
+    ).unwrap();
+    assert_eq!(trigger_of(&engine, stop.order_id), Some(95));
+
+    // One sell sweeps 110, 105, 100: the 110 print ratchets to 105, the 105 print fires
+    for price in [110, 105, 100] {
+        engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, price, 1)).unwrap();
+    }
+    let report = engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 100, 3)).unwrap();
+    assert_eq!(report.trades.iter().map(|t| t.price).collect::<Vec<_>>(), vec![110, 105, 100]);
+
+    assert_eq!(report.triggered.len(), 1);
+    assert_eq!(report.triggered[0].order_id, stop.order_id);
+    assert_eq!(report.triggered[0].filled_quantity, 20);
+}
+
+#[test]
+fn test_buy_trailing_stop_with_percentage_offset() {
+    let mut engine = MatchingEngine::new();
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 20000, 100)).unwrap();
+
+    trade_at(&mut engine, 10000);
+    let stop = engine.submit_order(
+        Order::trailing_stop("AAPL".to_string(), Side::Buy, TrailingOffset::BasisPoints(100), 10),
+    ).unwrap();
+    assert_eq!(trigger_of(&engine, stop.order_id), Some(10100), "1% above 10000");
+
+    // Falling price drags a buy stop down
+    trade_at(&mut engine, 9800);
+    assert_eq!(trigger_of(&engine, stop.order_id), Some(9898));
+
+    // Bounce of 1% fires it
+    trade_at(&mut engine, 9850);
+    assert_eq!(trigger_of(&engine, stop.order_id), Some(9898));
+    engine.submit_order(Order::limit("AAPL".to_string(), Side::Sell, 9900, 1)).unwrap();
+    let report = engine.submit_order(Order::limit("AAPL".to_string(), Side::Buy, 9900, 1)).unwrap();
+    assert_eq!(report.triggered.len(), 1);
+    assert_eq!(report.triggered[0].filled_quantity, 10);
+    assert_eq!(report.triggered[0].trades[0].price, 20000);
+}
+
+#[test]
+fn test_reject_trailing_stop_without_reference_price() {
+    let mut engine = MatchingEngine::new();
+
+    let stop = Order::trailing_stop("AAPL".to_string(), Side::Sell, TrailingOffset::Absolute(100), 10);
+    assert_eq!(engine.submit_order(stop).unwrap_err(), OrderError::NoReferencePrice);
+}
+
+#[test]
+fn test_reject_invalid_trailing_offset() {
+    let mut engine = MatchingEngine::new();
+    trade_at(&mut engine, 15000);
+
+    for offset in [TrailingOffset::Absolute(0), TrailingOffset::BasisPoints(0), TrailingOffset::BasisPoints(10_000)] {
+        let stop = Order::trailing_stop("AAPL".to_string(), Side::Sell, offset, 10);
+        assert_eq!(engine.submit_order(stop).unwrap_err(), OrderError::InvalidTrailingOffset);
+    }
+}
@@ -1,4 +1,4 @@
-use rustex::{MatchingEngine, Order, Side};
+use rustex::{ExecutionReport, MatchingEngine, Order, OrderError, Side};
 
 #[test]
 fn test_reject_zero_quantity() {
@@ -7,14 +7,14 @@ fn test_reject_zero_quantity() {
     let limit_order = Order::limit("AAPL".to_string(), Side::Buy, 15000, 0);
 
     match engine.submit_order(limit_order) {
-        Err(msg) => assert!(msg.contains("Invalid order: Quantity must be greater than 0")),
+        Err(e) => assert_eq!(e, OrderError::ZeroQuantity),
         Ok(_) => panic!("Should have rejected zero quantity"),
     }
 
     let market_order = Order::market("AAPL".to_string(), Side::Buy, 0);
     
     match engine.submit_order(market_order) {
-        Err(msg) => assert!(msg.contains("Invalid order: Quantity must be greater than 0")),
+        Err(e) => assert_eq!(e, OrderError::ZeroQuantity),
         Ok(_) => panic!("Should have rejected zero quantity"),
     }
 }
@@ -26,7 +26,7 @@ fn test_reject_zero_price() {
     let limit_order = Order::limit("AAPL".to_string(), Side::Buy, 0, 15000);
     
     match engine.submit_order(limit_order) {
-        Err(msg) => assert!(msg.contains("Invalid order: Price must be greater than 0")),
+        Err(e) => assert_eq!(e, OrderError::ZeroPrice),
         Ok(_) => panic!("Should have rejected zero price"),
     }
 
@@ -34,7 +34,7 @@ fn test_reject_zero_price() {
     limit_order_null_price.price = None;
 
     match engine.submit_order(limit_order_null_price) {
-        Err(msg) => assert!(msg.contains("Invalid order: Limit order requires price")),
+        Err(e) => assert_eq!(e, OrderError::MissingLimitPrice),
         Ok(_) => panic!("Should have rejected null price"),
     }
 }
@@ -46,14 +46,14 @@ fn test_reject_empty_symbol() {
     let limit_order = Order::limit("".to_string(), Side::Buy, 1500, 1500);
 
     match engine.submit_order(limit_order) {
-        Err(msg) => assert!(msg.contains("Invalid order: Symbol cannot be empty")),
+        Err(e) => assert_eq!(e, OrderError::EmptySymbol),
         Ok(_) => panic!("Should have rejected invalid symbol"),
     }
 
     let market_order = Order::market("".to_string(), Side::Buy, 1500);
     
     match engine.submit_order(market_order) {
-        Err(msg) => assert!(msg.contains("Invalid order: Symbol cannot be empty")),
+        Err(e) => assert_eq!(e, OrderError::EmptySymbol),
         Ok(_) => panic!("Should have rejected invalid symbol"),
     }
 }
@@ -65,14 +65,24 @@ fn test_valid_orders() {
     let limit_order = Order::limit("AAPL".to_string(), Side::Buy, 150, 150);
 
     match engine.submit_order(limit_order) {
-        Ok(trades) => assert!(trades.is_empty()),
+        Ok(ExecutionReport { trades, .. }) => assert!(trades.is_empty()),
         Err(e) => panic!("Order should be valid but failed: {e}"),
     }
 
     let market_order = Order::market("AAPL".to_string(), Side::Buy, 150);
 
     match engine.submit_order(market_order) {
-        Ok(trades) => assert!(trades.is_empty()),
+        Ok(ExecutionReport { trades, .. }) => assert!(trades.is_empty()),
         Err(e) => panic!("Order should be valid but failed: {e}"),
     }
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_rejection_implements_error() {
+    let mut engine = MatchingEngine::new();
+
+    let order = Order::limit("AAPL".to_string(), Side::Buy, 15000, 0);
+    let err: Box<dyn std::error::Error> = Box::new(engine.submit_order(order).unwrap_err());
+
+    assert_eq!(err.to_string(), "Invalid order: Quantity must be greater than 0");
+}